@@ -0,0 +1,10 @@
+#![no_main]
+
+#[allow(dead_code)]
+#[path = "../../src/bin/01.rs"]
+mod day;
+
+libfuzzer_sys::fuzz_target!(|input: &str| {
+    let _ = day::part_one(input);
+    let _ = day::part_two(input);
+});
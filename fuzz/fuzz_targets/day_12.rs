@@ -0,0 +1,9 @@
+#![no_main]
+
+#[allow(dead_code)]
+#[path = "../../src/bin/12.rs"]
+mod day;
+
+libfuzzer_sys::fuzz_target!(|input: &str| {
+    let _ = day::part_one(input);
+});
@@ -0,0 +1,524 @@
+//! Cellular automata: worlds of cells whose next state depends on their
+//! neighbors.
+//!
+//! A [`World`] knows how to read and write cells and which cells neighbor each
+//! other. It comes as a dense [`Grid`] of any cell state, or as a [`Sparse`]
+//! set of live cells on an unbounded lattice of [`Point2`], [`Point3`] or
+//! [`PointN`] points. An [`Automaton`] applies a rule to a world, either to
+//! every cell at once ([`Automaton::step`]) or one cell at a time from a
+//! worklist of cells whose neighbors changed ([`Automaton::settle`]).
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use crate::cycle::{self, Cycle};
+use crate::point::{Point2, Point3, PointN, Signed};
+
+/// Which cells count as neighbors.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Neighborhood {
+    /// The cells one step away along an axis (von Neumann).
+    Orthogonal,
+    /// The cells touching, including diagonally.
+    Moore,
+}
+
+/// A point type that can enumerate its neighbors on an unbounded lattice.
+pub trait Lattice: Copy + Eq + Hash {
+    fn neighbors(self, neighborhood: Neighborhood) -> impl Iterator<Item = Self>;
+}
+
+impl<T: Signed + Eq + Hash> Lattice for Point2<T> {
+    fn neighbors(self, neighborhood: Neighborhood) -> impl Iterator<Item = Self> {
+        Self::dirs8()
+            .into_iter()
+            .filter(move |&d| {
+                neighborhood == Neighborhood::Moore || d.x == T::ZERO || d.y == T::ZERO
+            })
+            .map(move |d| self + d)
+    }
+}
+
+impl<T: Signed + Eq + Hash, const D: usize> Lattice for PointN<T, D> {
+    fn neighbors(self, neighborhood: Neighborhood) -> impl Iterator<Item = Self> {
+        Self::moore_dirs()
+            .filter(move |&d| {
+                neighborhood == Neighborhood::Moore || d.manhattan(Self::zero()) == T::ONE
+            })
+            .map(move |d| self + d)
+    }
+}
+
+impl<T: Signed + Eq + Hash> Lattice for Point3<T> {
+    fn neighbors(self, neighborhood: Neighborhood) -> impl Iterator<Item = Self> {
+        PointN::from(self).neighbors(neighborhood).map(Point3::from)
+    }
+}
+
+/// The cells of an automaton and how they are connected.
+pub trait World: Clone {
+    type Cell: Copy + Eq + Hash;
+    type State: Copy + Eq;
+
+    fn get(&self, cell: Self::Cell) -> Self::State;
+
+    fn set(&mut self, cell: Self::Cell, state: Self::State);
+
+    fn neighbors(&self, cell: Self::Cell) -> impl Iterator<Item = Self::Cell>;
+
+    /// Every cell that might change in the next generation.
+    fn candidates(&self) -> impl Iterator<Item = Self::Cell>;
+
+    /// How many neighbors of `cell` are in `state`.
+    fn count_neighbors(&self, cell: Self::Cell, state: Self::State) -> usize {
+        self.neighbors(cell)
+            .filter(|&n| self.get(n) == state)
+            .count()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular grid holding a state for every cell. Cells outside the grid
+/// have no neighbors and don't exist.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Grid<S> {
+    width: usize,
+    height: usize,
+    cells: Vec<S>,
+    neighborhood: Neighborhood,
+}
+
+impl<S: Copy + Eq> Grid<S> {
+    pub fn new(width: usize, height: usize, fill: S, neighborhood: Neighborhood) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+            neighborhood,
+        }
+    }
+
+    /// Builds the grid from its rows, returns [`None`] if they don't all have the same length.
+    pub fn from_rows(rows: Vec<Vec<S>>, neighborhood: Neighborhood) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+            neighborhood,
+        })
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn contains(&self, p: Point2<usize>) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    /// Every cell with its state, top-to-bottom, left-to-right.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2<usize>, S)> + '_ {
+        self.positions().map(|p| (p, self.get(p)))
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Point2<usize>> + use<S> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point2::new(x, y)))
+    }
+}
+
+impl<S: Copy + Eq> World for Grid<S> {
+    type Cell = Point2<usize>;
+    type State = S;
+
+    #[inline]
+    fn get(&self, p: Point2<usize>) -> S {
+        self.cells[p.y * self.width + p.x]
+    }
+
+    #[inline]
+    fn set(&mut self, p: Point2<usize>, state: S) {
+        self.cells[p.y * self.width + p.x] = state;
+    }
+
+    fn neighbors(&self, p: Point2<usize>) -> impl Iterator<Item = Point2<usize>> {
+        Point2::zero()
+            .neighbors(self.neighborhood)
+            .filter_map(move |d| p.checked_add_signed(d))
+            .filter(|&n| self.contains(n))
+    }
+
+    fn candidates(&self) -> impl Iterator<Item = Point2<usize>> {
+        self.positions()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The live cells of an unbounded lattice, where every other cell is dead.
+#[derive(Debug, Clone)]
+pub struct Sparse<P> {
+    live: HashSet<P>,
+    neighborhood: Neighborhood,
+}
+
+impl<P: Lattice> Sparse<P> {
+    pub fn new(live: impl IntoIterator<Item = P>, neighborhood: Neighborhood) -> Self {
+        Self {
+            live: live.into_iter().collect(),
+            neighborhood,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.live.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.live.is_empty()
+    }
+
+    /// The live cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = P> + '_ {
+        self.live.iter().copied()
+    }
+}
+
+impl<P: Lattice> PartialEq for Sparse<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.live == other.live && self.neighborhood == other.neighborhood
+    }
+}
+
+impl<P: Lattice> Eq for Sparse<P> {}
+
+/// Hashes the live cells regardless of the order the set keeps them in.
+impl<P: Hash> Hash for Sparse<P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let combined = self.live.iter().fold(0u64, |acc, p| {
+            let mut hasher = DefaultHasher::new();
+            p.hash(&mut hasher);
+            acc.wrapping_add(hasher.finish())
+        });
+        self.live.len().hash(state);
+        combined.hash(state);
+        self.neighborhood.hash(state);
+    }
+}
+
+impl<P: Lattice> World for Sparse<P> {
+    type Cell = P;
+    type State = bool;
+
+    #[inline]
+    fn get(&self, p: P) -> bool {
+        self.live.contains(&p)
+    }
+
+    #[inline]
+    fn set(&mut self, p: P, live: bool) {
+        if live {
+            self.live.insert(p);
+        } else {
+            self.live.remove(&p);
+        }
+    }
+
+    fn neighbors(&self, p: P) -> impl Iterator<Item = P> {
+        p.neighbors(self.neighborhood)
+    }
+
+    /// The live cells and every cell next to one.
+    fn candidates(&self) -> impl Iterator<Item = P> {
+        let mut cells = self.live.clone();
+        for &p in &self.live {
+            cells.extend(self.neighbors(p));
+        }
+        cells.into_iter()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A world together with the rule that gives the next state of a cell.
+pub struct Automaton<W, R> {
+    world: W,
+    rule: R,
+    generation: usize,
+}
+
+impl<W, R> Automaton<W, R>
+where
+    W: World,
+    R: Fn(&W, W::Cell) -> W::State,
+{
+    pub fn new(world: W, rule: R) -> Self {
+        Self {
+            world,
+            rule,
+            generation: 0,
+        }
+    }
+
+    #[inline]
+    pub fn world(&self) -> &W {
+        &self.world
+    }
+
+    #[inline]
+    pub fn into_world(self) -> W {
+        self.world
+    }
+
+    /// How many synchronous steps have been taken.
+    #[inline]
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Updates every cell at once from the current states. Returns how many
+    /// cells changed.
+    pub fn step(&mut self) -> usize {
+        let changes = self.changes(&self.world);
+        for &(cell, state) in &changes {
+            self.world.set(cell, state);
+        }
+        self.generation += 1;
+        changes.len()
+    }
+
+    /// The world one generation after `world`.
+    fn next(&self, world: &W) -> W {
+        let mut next = world.clone();
+        for (cell, state) in self.changes(world) {
+            next.set(cell, state);
+        }
+        next
+    }
+
+    fn changes(&self, world: &W) -> Vec<(W::Cell, W::State)> {
+        world
+            .candidates()
+            .filter_map(|cell| {
+                let next = (self.rule)(world, cell);
+                (next != world.get(cell)).then_some((cell, next))
+            })
+            .collect()
+    }
+
+    /// Steps until nothing changes, and returns the generation of the fixed
+    /// point. Never returns if the automaton oscillates, see [`Automaton::find_cycle`].
+    pub fn run_until_stable(&mut self) -> usize {
+        while self.step() > 0 {}
+        self.generation - 1
+    }
+
+    /// Updates cells one at a time in place, each seeing the changes made
+    /// before it, until no cell changes. After the first pass over every
+    /// candidate, only the neighbors of changed cells are looked at again.
+    /// Returns how many updates were made.
+    ///
+    /// This reaches the same state as [`Automaton::run_until_stable`] for
+    /// rules where the order of updates doesn't matter, like removing cells
+    /// with too few neighbors, and is much faster when changes are local.
+    pub fn settle(&mut self) -> usize {
+        let mut queue: VecDeque<_> = self.world.candidates().collect();
+        let mut queued: HashSet<_> = queue.iter().copied().collect();
+        let mut updates = 0;
+        while let Some(cell) = queue.pop_front() {
+            queued.remove(&cell);
+            let next = (self.rule)(&self.world, cell);
+            if next != self.world.get(cell) {
+                self.world.set(cell, next);
+                updates += 1;
+                for n in self.world.neighbors(cell) {
+                    if queued.insert(n) {
+                        queue.push_back(n);
+                    }
+                }
+            }
+        }
+        updates
+    }
+}
+
+impl<W, R> Automaton<W, R>
+where
+    W: World + Eq + Hash,
+    R: Fn(&W, W::Cell) -> W::State,
+{
+    /// Steps until a state repeats, remembering every state seen on the way.
+    /// The cycle counts generations from the start of the automaton.
+    pub fn find_cycle(&mut self) -> Cycle {
+        let (cycle, mut states) = cycle::detect(self.world.clone(), |world| self.next(world));
+        self.generation += states.len() - 1;
+        self.world = states.pop().expect("the repeated state");
+        Cycle {
+            start: self.generation - cycle.length,
+            length: cycle.length,
+        }
+    }
+
+    /// Steps to `generation`, skipping whole cycles once the states start
+    /// repeating. Does nothing if the automaton is already past it.
+    pub fn advance_to(&mut self, generation: usize) {
+        let steps = generation.saturating_sub(self.generation);
+        self.world = cycle::state_at(self.world.clone(), |world| self.next(world), steps);
+        self.generation += steps;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential::forall;
+
+    fn life<W: World<State = bool>>(world: &W, cell: W::Cell) -> bool {
+        match world.count_neighbors(cell, true) {
+            3 => true,
+            2 => world.get(cell),
+            _ => false,
+        }
+    }
+
+    fn parse<P: Lattice>(input: &str, point: impl Fn(i32, i32) -> P) -> Sparse<P> {
+        let live = input.lines().enumerate().flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '#')
+                .map(move |(x, _)| (x as i32, y as i32))
+        });
+        Sparse::new(
+            live.map(|(x, y)| point(x, y)).collect::<Vec<_>>(),
+            Neighborhood::Moore,
+        )
+    }
+
+    #[test]
+    fn counts_lattice_neighbors() {
+        let count = |p: Point2<i32>, n| p.neighbors(n).count();
+        assert_eq!(count(Point2::zero(), Neighborhood::Orthogonal), 4);
+        assert_eq!(count(Point2::zero(), Neighborhood::Moore), 8);
+        assert_eq!(
+            Point3::new(1, 2, 3)
+                .neighbors(Neighborhood::Orthogonal)
+                .count(),
+            6
+        );
+        assert_eq!(
+            Point3::new(1, 2, 3).neighbors(Neighborhood::Moore).count(),
+            26
+        );
+        assert_eq!(
+            PointN::new([0i8; 4]).neighbors(Neighborhood::Moore).count(),
+            80
+        );
+        let grid = Grid::new(3, 2, false, Neighborhood::Moore);
+        assert_eq!(grid.neighbors(Point2::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbors(Point2::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn glider_moves_diagonally() {
+        let glider = parse(".#.\n..#\n###", Point2::new);
+        let mut automaton = Automaton::new(glider.clone(), life);
+        for _ in 0..4 {
+            automaton.step();
+        }
+        let moved: HashSet<_> = glider.iter().map(|p| p + Point2::new(1, 1)).collect();
+        assert_eq!(automaton.world().iter().collect::<HashSet<_>>(), moved);
+    }
+
+    #[test]
+    fn blinker_cycles_on_a_grid() {
+        let rows = [".....", "..#..", "..#..", "..#..", "....."]
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .to_vec();
+        let grid = Grid::from_rows(rows, Neighborhood::Moore).unwrap();
+        let mut automaton = Automaton::new(grid.clone(), life);
+        assert_eq!(
+            automaton.find_cycle(),
+            Cycle {
+                start: 0,
+                length: 2
+            }
+        );
+        automaton.advance_to(1001);
+        assert_eq!(automaton.generation(), 1001);
+        assert_ne!(automaton.world(), &grid);
+        automaton.advance_to(2000);
+        assert_eq!(automaton.world(), &grid);
+        assert!(Grid::from_rows(vec![vec![true], vec![]], Neighborhood::Moore).is_none());
+    }
+
+    #[test]
+    fn conway_cubes_in_three_dimensions() {
+        let cubes = parse(".#.\n..#\n###", |x, y| Point3::new(x, y, 0));
+        let mut automaton = Automaton::new(cubes, life);
+        for _ in 0..6 {
+            automaton.step();
+        }
+        assert_eq!(automaton.world().len(), 112);
+
+        let cubes = parse(".#.\n..#\n###", |x, y| PointN::new([x, y, 0, 0]));
+        let mut automaton = Automaton::new(cubes, life);
+        automaton.step();
+        assert_eq!(automaton.world().len(), 29); // 9 layers of 3 or 5 cubes
+    }
+
+    #[test]
+    fn fixed_points_are_cycles_of_length_one() {
+        let block = parse("##\n##", Point2::new);
+        let mut automaton = Automaton::new(block, life);
+        assert_eq!(automaton.run_until_stable(), 0);
+        assert_eq!(
+            automaton.find_cycle(),
+            Cycle {
+                start: 1,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn settle_matches_synchronous_removal() {
+        forall(
+            |g| g.vec(|g| g.vec(|g| g.range(0..=2) > 0)),
+            |rows| {
+                let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+                let rows: Vec<Vec<bool>> = rows
+                    .iter()
+                    .map(|row| (0..width).map(|x| row.get(x) == Some(&true)).collect())
+                    .collect();
+                let Some(grid) = Grid::from_rows(rows, Neighborhood::Moore) else {
+                    return false;
+                };
+                let erode =
+                    |grid: &Grid<bool>, p| grid.get(p) && grid.count_neighbors(p, true) >= 4;
+
+                let mut sync = Automaton::new(grid.clone(), erode);
+                sync.run_until_stable();
+                let mut worklist = Automaton::new(grid.clone(), erode);
+                let removed = worklist.settle();
+
+                let live = |grid: &Grid<bool>| grid.iter().filter(|&(_, live)| live).count();
+                sync.world() == worklist.world() && live(&grid) - removed == live(worklist.world())
+            },
+        );
+    }
+}
@@ -1,44 +1,39 @@
+use advent_of_code::dial::Dial;
+use advent_of_code::parse;
 use sscanf::sscanf;
-use std::iter::repeat_n;
 
 advent_of_code::solution!(1);
 
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(
-        parse_rotations(input)
-            .iter()
-            .scan(50, |acc, rot| {
-                *acc = (*acc + rot).rem_euclid(100);
-                Some(*acc)
-            })
-            .filter(|x| *x == 0)
-            .count(),
-    )
+pub fn part_one(input: &str) -> parse::Result<usize> {
+    let mut dial = Dial::new(100, 50);
+    Ok(parse_rotations(input)?
+        .into_iter()
+        .filter(|rot| {
+            dial.rotate(*rot);
+            dial.position() == 0
+        })
+        .count())
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
-    Some(
-        parse_rotations(input)
-            .iter()
-            .flat_map(|n| {
-                let d = if *n < 0 { -1i32 } else { 1i32 };
-                repeat_n(d, n.unsigned_abs() as usize)
-            })
-            .fold((0, 50), |(zeroes, pos), d| {
-                let pos = (pos + d).rem_euclid(100);
-                (zeroes + if pos == 0 { 1 } else { 0 }, pos)
-            })
-            .0,
-    )
+pub fn part_two(input: &str) -> parse::Result<u64> {
+    let mut dial = Dial::new(100, 50);
+    Ok(parse_rotations(input)?
+        .into_iter()
+        .map(|rot| dial.rotate(rot))
+        .sum())
 }
 
-fn parse_rotations(s: &str) -> Vec<i32> {
-    s.lines()
-        .filter_map(|line| {
-            let (dir, n) = sscanf!(line, "{}{}", char, i32).ok()?;
-            Some(if dir == 'L' { -n } else { n })
-        })
-        .collect()
+fn parse_rotations(input: &str) -> parse::Result<Vec<i64>> {
+    parse::lines(input, |line| {
+        let (dir, n) = line.scan(|s| sscanf!(s, "{}{}", char, i64))?;
+        match dir {
+            'L' => n
+                .checked_neg()
+                .ok_or_else(|| line.error("rotation out of range")),
+            'R' => Ok(n),
+            _ => Err(line.error(format!("unknown direction {dir:?}"))),
+        }
+    })
 }
 
 #[cfg(test)]
@@ -48,15 +43,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one(&read_file("examples", DAY)), Some(3));
+        assert_eq!(part_one(&read_file("examples", DAY)).ok(), Some(3));
         #[cfg(not(feature = "ci"))]
-        assert_eq!(part_one(&read_file("inputs", DAY)), Some(1195));
+        assert_eq!(part_one(&read_file("inputs", DAY)).ok(), Some(1195));
     }
 
     #[test]
     fn test_part_two() {
-        assert_eq!(part_two(&read_file("examples", DAY)), Some(6));
+        assert_eq!(part_two(&read_file("examples", DAY)).ok(), Some(6));
         #[cfg(not(feature = "ci"))]
-        assert_eq!(part_two(&read_file("inputs", DAY)), Some(6770));
+        assert_eq!(part_two(&read_file("inputs", DAY)).ok(), Some(6770));
     }
 }
@@ -1,52 +1,32 @@
-use rayon::prelude::*;
+use advent_of_code::periodic::{sum_periodic, sum_repeated};
 use sscanf::sscanf;
 
 advent_of_code::solution!(2);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    sum_invalid(input, |x| {
-        let s = x.to_string();
-        if !s.len().is_multiple_of(2) {
-            return false;
-        }
-        let (rhs, lhs) = s.split_at(s.len() / 2);
-        lhs == rhs
+    sum_invalid(input, |lo, hi| {
+        (1..=10).map(|k| sum_repeated(lo, hi, k, 2)).sum()
     })
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    sum_invalid(input, |x| {
-        let chars: Vec<char> = x.to_string().chars().collect();
-        for i in 1..=chars.len() / 2 {
-            if !chars.len().is_multiple_of(i) {
-                continue;
-            }
-            let mut iter = chars.chunks_exact(i);
-            let first = iter.next().unwrap();
-            if iter.all(|chunk| chunk == first) {
-                return true;
-            }
-        }
-        false
-    })
+    sum_invalid(input, sum_periodic)
 }
 
-fn sum_invalid<P>(input: &str, p: P) -> Option<u64>
+fn sum_invalid<F>(input: &str, f: F) -> Option<u64>
 where
-    P: Send + Sync + Fn(&u64) -> bool,
+    F: Fn(u64, u64) -> u128,
 {
-    Some(
-        input
-            .trim()
-            .par_split(',')
-            .filter_map(|range| {
-                let (lo, hi) = sscanf!(range, "{}-{}", u64, u64).ok()?;
-                Some((lo, hi))
-            })
-            .flat_map(|(lo, hi)| lo..=hi)
-            .filter(p)
-            .sum(),
-    )
+    input
+        .trim()
+        .split(',')
+        .filter_map(|range| {
+            let (lo, hi) = sscanf!(range, "{}-{}", u64, u64).ok()?;
+            Some(f(lo, hi))
+        })
+        .sum::<u128>()
+        .try_into()
+        .ok()
 }
 
 #[cfg(test)]
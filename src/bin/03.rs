@@ -1,3 +1,5 @@
+use advent_of_code::digits::largest_subsequence;
+
 advent_of_code::solution!(3);
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -9,25 +11,15 @@ pub fn part_two(input: &str) -> Option<u64> {
 }
 
 fn solve(input: &str, width: usize) -> u64 {
-    fn go(digits: &[u64], acc: u64, n: usize) -> Option<u64> {
-        if n == 0 {
-            Some(acc)
-        } else {
-            digits[..=digits.len() - n]
-                .iter()
-                .enumerate()
-                // make max_by_key return the first max in case of ties
-                .rev()
-                .max_by_key(|(_, d)| *d)
-                .and_then(|(i, d)| go(&digits[i + 1..], 10 * acc + d, n - 1))
-        }
-    }
-
     input
         .lines()
         .flat_map(|line| {
-            let digits: Vec<u64> = line.chars().map(|c| c as u64 - '0' as u64).collect();
-            go(&digits, 0, width)
+            let digits: Vec<u64> = line
+                .chars()
+                .filter_map(|c| c.to_digit(10))
+                .map(u64::from)
+                .collect();
+            largest_subsequence(&digits, width, 10)
         })
         .sum()
 }
@@ -35,7 +27,39 @@ fn solve(input: &str, width: usize) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::{Differential, Gen};
     use advent_of_code::template::read_file;
+    use itertools::Itertools;
+
+    /// Banks of batteries with joltages `1..=9`.
+    type Banks = Vec<Vec<u8>>;
+
+    fn generate(g: &mut Gen) -> Banks {
+        g.vec(|g| {
+            let len = g.range(0..=16);
+            (0..len).map(|_| g.range(1..=9) as u8).collect()
+        })
+    }
+
+    fn render(banks: &Banks) -> String {
+        banks
+            .iter()
+            .map(|bank| bank.iter().map(u8::to_string).collect::<String>())
+            .join("\n")
+    }
+
+    /// Tries every choice of `width` batteries in every bank.
+    fn naive(input: &str, width: usize) -> u64 {
+        input
+            .lines()
+            .flat_map(|line| {
+                line.bytes()
+                    .combinations(width)
+                    .map(|digits| digits.iter().fold(0, |n, d| 10 * n + u64::from(d - b'0')))
+                    .max()
+            })
+            .sum()
+    }
 
     #[test]
     fn test_part_one() {
@@ -50,4 +74,11 @@ mod tests {
         #[cfg(not(feature = "ci"))]
         assert_eq!(part_two(&read_file("inputs", DAY)), Some(175053592950232));
     }
+
+    #[test]
+    fn test_against_reference() {
+        let check = || Differential::new(generate, render);
+        check().check(|input| Some(naive(input, 2)), part_one);
+        check().check(|input| Some(naive(input, 12)), part_two);
+    }
 }
@@ -1,63 +1,42 @@
+use advent_of_code::automaton::{Automaton, Neighborhood, Sparse, World};
 use advent_of_code::point::Point2;
-use std::collections::{HashSet, VecDeque};
 
 advent_of_code::solution!(4);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    Some(accessible_rolls(&parse_rolls(input)).count() as u64)
+    let rolls = parse_rolls(input);
+    Some(rolls.iter().filter(|&p| is_accessible(&rolls, p)).count() as u64)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    Some(remove_accessible_rolls(&mut parse_rolls(input)))
+    let mut automaton = Automaton::new(parse_rolls(input), keep_roll);
+    Some(automaton.settle() as u64)
 }
 
 type Point = Point2<isize>;
+type Rolls = Sparse<Point>;
 
-fn remove_accessible_rolls(rolls: &mut HashSet<Point>) -> u64 {
-    let mut count = 0;
-    let mut queue: VecDeque<_> = accessible_rolls(rolls).collect();
-    while let Some(p) = queue.pop_front() {
-        if !rolls.contains(&p) {
-            continue;
-        }
-        if is_accessible(rolls, p) {
-            count += 1;
-            rolls.remove(&p);
-            queue.extend(neighbors(rolls, p));
-        }
-    }
-    count
-}
-
-fn accessible_rolls(rolls: &HashSet<Point>) -> impl Iterator<Item = Point> {
-    rolls
-        .iter()
-        .copied()
-        .filter(move |p| is_accessible(rolls, *p))
-}
-
-fn is_accessible(rolls: &HashSet<Point>, p: Point) -> bool {
-    neighbors(&rolls, p).count() < 4
+/// Removing a roll only makes its neighbors more accessible, so the rolls
+/// can be removed in any order.
+fn keep_roll(rolls: &Rolls, p: Point) -> bool {
+    rolls.get(p) && !is_accessible(rolls, p)
 }
 
-fn neighbors(rolls: &HashSet<Point>, p: Point) -> impl Iterator<Item = Point> {
-    p.neighbors8().into_iter().filter(|q| rolls.contains(&q))
+fn is_accessible(rolls: &Rolls, p: Point) -> bool {
+    rolls.count_neighbors(p, true) < 4
 }
 
-fn parse_rolls(input: &str) -> HashSet<Point> {
-    input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().filter_map(move |(x, c)| {
-                if c == '@' {
-                    Some(Point2::new(x as isize, y as isize))
-                } else {
-                    None
-                }
-            })
+fn parse_rolls(input: &str) -> Rolls {
+    let rolls = input.lines().enumerate().flat_map(|(y, line)| {
+        line.chars().enumerate().filter_map(move |(x, c)| {
+            if c == '@' {
+                Some(Point2::new(x as isize, y as isize))
+            } else {
+                None
+            }
         })
-        .collect()
+    });
+    Sparse::new(rolls, Neighborhood::Moore)
 }
 
 #[cfg(test)]
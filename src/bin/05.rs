@@ -15,12 +15,9 @@ pub fn part_one(input: &str) -> Option<u64> {
 pub fn part_two(input: &str) -> Option<u64> {
     let (mut ranges, _) = parse(input)?;
     merge_intervals(&mut ranges);
-    Some(
-        ranges
-            .into_iter()
-            .map(|(lo, hi)| 1 + hi - lo)
-            .sum::<usize>() as u64,
-    )
+    ranges.into_iter().try_fold(0, |total: u64, (lo, hi)| {
+        total.checked_add((hi - lo) as u64)?.checked_add(1)
+    })
 }
 
 fn merge_intervals(ranges: &mut Vec<Interval>) {
@@ -54,6 +51,7 @@ fn parse(input: &str) -> Option<(Vec<Interval>, Vec<usize>)> {
     let ranges: Vec<_> = ranges
         .lines()
         .flat_map(|line| sscanf!(line, "{}-{}", usize, usize))
+        .filter(|(lo, hi)| lo <= hi)
         .collect();
     let ids = ids.lines().flat_map(|line| line.parse()).collect();
     Some((ranges, ids))
@@ -64,7 +62,43 @@ type Interval = (usize, usize);
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::{Differential, Gen};
     use advent_of_code::template::read_file;
+    use std::collections::BTreeSet;
+
+    /// Ranges as a start and a length, so that shrinking keeps them valid, and ids.
+    type Database = (Vec<(u64, u64)>, Vec<u64>);
+
+    fn generate(g: &mut Gen) -> Database {
+        let ranges = g.vec(|g| (g.range(0..=50), g.range(0..=10)));
+        let ids = g.vec(|g| g.range(0..=60));
+        (ranges, ids)
+    }
+
+    fn render((ranges, ids): &Database) -> String {
+        let ranges: Vec<String> = ranges
+            .iter()
+            .map(|(lo, len)| format!("{lo}-{}", lo + len))
+            .collect();
+        let ids: Vec<String> = ids.iter().map(u64::to_string).collect();
+        format!("{}\n\n{}", ranges.join("\n"), ids.join("\n"))
+    }
+
+    /// Every id covered by a range, one at a time.
+    fn naive_fresh(input: &str) -> BTreeSet<usize> {
+        let (ranges, _) = parse(input).unwrap();
+        ranges.into_iter().flat_map(|(lo, hi)| lo..=hi).collect()
+    }
+
+    fn naive_part_one(input: &str) -> Option<u64> {
+        let fresh = naive_fresh(input);
+        let (_, ids) = parse(input)?;
+        Some(ids.iter().filter(|id| fresh.contains(id)).count() as u64)
+    }
+
+    fn naive_part_two(input: &str) -> Option<u64> {
+        Some(naive_fresh(input).len() as u64)
+    }
 
     #[test]
     fn test_part_one() {
@@ -79,4 +113,11 @@ mod tests {
         #[cfg(not(feature = "ci"))]
         assert_eq!(part_two(&read_file("inputs", DAY)), Some(344323629240733));
     }
+
+    #[test]
+    fn test_against_reference() {
+        let check = || Differential::new(generate, render);
+        check().check(naive_part_one, part_one);
+        check().check(naive_part_two, part_two);
+    }
 }
@@ -1,68 +1,32 @@
+use advent_of_code::columns::{Block, blocks};
+
 advent_of_code::solution!(6);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut lines: Vec<&str> = input.lines().collect();
-    let last = lines.pop()?;
-
-    let mut grid: Vec<_> = lines
-        .into_iter()
-        .map(|line| line.split_whitespace().flat_map(|n| n.parse::<u64>().ok()))
-        .collect();
-
-    Some(
-        last.split_whitespace()
-            .flat_map(|s| {
-                let col = grid.iter_mut().filter_map(|col| col.next());
-                Some(match s.parse().ok()? {
-                    Op::Add => col.sum::<u64>(),
-                    Op::Mul => col.product(),
-                })
-            })
-            .sum(),
-    )
+    solve(input, |block| block.rows().collect())
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut lines: Vec<&str> = input.lines().collect();
-    let mut iter = lines.pop()?.chars().enumerate().peekable();
-
-    let mut ops: Vec<(Op, usize, usize)> = vec![];
-    while let Some((col, op @ ('+' | '*'))) = iter.next() {
-        let mut span = 1;
-        while let Some((_, c)) = iter.peek() {
-            if c.is_whitespace() {
-                iter.next();
-                span += 1;
-            } else {
-                span -= 1; // scooch back, we hit the next one
-                break;
-            }
-        }
-        ops.push((op.to_string().parse().ok()?, col, span));
-    }
+    solve(input, |block| block.columns().rev().collect())
+}
 
-    let mut grid: Vec<Vec<u64>> = ops.iter().map(|(_, _, span)| vec![0; *span]).collect();
+fn solve(input: &str, read: impl Fn(&Block) -> Vec<String>) -> Option<u64> {
+    let mut lines: Vec<&str> = input.lines().collect();
+    let ops = lines.pop()?;
 
-    for line in lines.into_iter() {
-        let chars: Vec<char> = line.chars().collect();
-        for (i, (_, col, span)) in ops.iter().copied().enumerate() {
-            for j in 0..span {
-                if chars[col + j].is_digit(10) {
-                    grid[i][j] = grid[i][j] * 10 + chars[col + j] as u64 - '0' as u64;
-                }
+    blocks(lines)
+        .iter()
+        .zip(ops.split_whitespace())
+        .map(|(block, op)| {
+            let mut numbers = read(block)
+                .into_iter()
+                .flat_map(|n| n.trim().parse::<u64>().ok());
+            match op.parse().ok()? {
+                Op::Add => numbers.try_fold(0, u64::checked_add),
+                Op::Mul => numbers.try_fold(1, u64::checked_mul),
             }
-        }
-    }
-
-    Some(
-        ops.into_iter()
-            .enumerate()
-            .map(|(i, (op, _, _))| match op {
-                Op::Add => grid[i].iter().sum::<u64>(),
-                Op::Mul => grid[i].iter().product(),
-            })
-            .sum(),
-    )
+        })
+        .try_fold(0u64, |total, n| total.checked_add(n?))
 }
 
 #[derive(Copy, Clone)]
@@ -1,5 +1,6 @@
+use advent_of_code::memo::Memo;
 use advent_of_code::point::Point2;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 advent_of_code::solution!(7);
 
@@ -8,31 +9,31 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    Some(solve(&parse(&input)?).paths)
+    solve(&parse(&input)?).paths
 }
 
 fn solve(grid: &Grid) -> Answer {
-    fn go(splits: &mut u64, memo: &mut HashMap<Point, u64>, grid: &Grid, p: Point) -> u64 {
-        if let Some(paths) = memo.get(&p) {
-            return *paths;
-        }
-
-        let paths = if grid.splitters.contains(&p) {
-            *splits += 1;
-            go(splits, memo, grid, p.left()) + go(splits, memo, grid, p.right())
+    // a splitter next to another one sends the beam back and forth forever,
+    // so there is no finite number of paths
+    let mut paths = Memo::new(|paths: &mut dyn FnMut(Point) -> Option<u64>, p: Point| {
+        if grid.splitters.contains(&p) {
+            // visit both sides even if one loops, so that part one sees every splitter
+            let (left, right) = (paths(p.left()), paths(p.right()));
+            left?.checked_add(right?)
         } else if p.y < grid.height {
-            go(splits, memo, grid, p.down())
+            paths(p.down())
         } else {
-            1
-        };
-        memo.insert(p, paths);
-        paths
+            Some(1)
+        }
+    })
+    .on_cycle(None);
+    let total = paths.get(grid.start);
+    // every splitter the beam reaches is visited exactly once
+    let splits = paths.keys().filter(|p| grid.splitters.contains(p)).count() as u64;
+    Answer {
+        splits,
+        paths: total,
     }
-
-    let mut splits = 0;
-    let mut memo = HashMap::new();
-    let paths = go(&mut splits, &mut memo, grid, grid.start);
-    Answer { splits, paths }
 }
 
 fn parse(input: &str) -> Option<Grid> {
@@ -66,7 +67,7 @@ struct Grid {
 
 struct Answer {
     splits: u64,
-    paths: u64,
+    paths: Option<u64>,
 }
 
 #[cfg(test)]
@@ -87,4 +88,11 @@ mod tests {
         #[cfg(not(feature = "ci"))]
         assert_eq!(part_two(&read_file("inputs", DAY)), Some(48989920237096));
     }
+
+    #[test]
+    fn test_adjacent_splitters() {
+        let input = "...S..\n...^..\n..^^^.\n......";
+        assert_eq!(part_one(input), Some(4));
+        assert_eq!(part_two(input), None);
+    }
 }
@@ -1,3 +1,6 @@
+use std::error::Error;
+
+use advent_of_code::parse;
 use advent_of_code::point::Point3;
 use disjoint::DisjointSet;
 use itertools::Itertools;
@@ -5,8 +8,8 @@ use sscanf::scanf;
 
 advent_of_code::solution!(8);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let points = parse(input);
+pub fn part_one(input: &str) -> Result<u64, Box<dyn Error>> {
+    let points = parse(input)?;
 
     // example only uses 10 pairs for some reason
     let wanted = if points.len() < 1000 { 10 } else { 1000 };
@@ -25,18 +28,16 @@ pub fn part_one(input: &str) -> Option<u64> {
         }
     }
 
-    Some(
-        circuits
-            .sets()
-            .into_iter()
-            .map(|circuit| circuit.len())
-            .k_largest(3)
-            .product::<usize>() as u64,
-    )
+    Ok(circuits
+        .sets()
+        .into_iter()
+        .map(|circuit| circuit.len())
+        .k_largest(3)
+        .product::<usize>() as u64)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let points = parse(input);
+pub fn part_two(input: &str) -> Result<u64, Box<dyn Error>> {
+    let points = parse(input)?;
     let pairs: Vec<(usize, usize)> = (0..points.len())
         .cartesian_product(0..points.len())
         .filter(|(i, j)| i < j)
@@ -51,23 +52,26 @@ pub fn part_two(input: &str) -> Option<u64> {
             circuits.join(i, j);
             num_circuits -= 1;
             if num_circuits <= 1 {
-                return Some((points[i].x * points[j].x) as u64);
+                return Ok((points[i].x * points[j].x) as u64);
             }
         }
     }
-    None
+    Err("the junction boxes never form a single circuit".into())
 }
 
 type Point = Point3<i64>;
 
-fn parse(input: &str) -> Vec<Point> {
-    input
-        .lines()
-        .flat_map(|line| {
-            let (x, y, z) = scanf!(line, "{},{},{}", i64, i64, i64).ok()?;
-            Some(Point3::new(x, y, z))
-        })
-        .collect()
+/// Keeps squared distances from overflowing.
+const MAX_COORDINATE: u64 = 1 << 29;
+
+fn parse(input: &str) -> parse::Result<Vec<Point>> {
+    parse::lines(input, |line| {
+        let (x, y, z) = line.scan(|s| scanf!(s, "{},{},{}", i64, i64, i64))?;
+        if [x, y, z].iter().any(|c| c.unsigned_abs() > MAX_COORDINATE) {
+            return Err(line.error(format!("coordinates must be within ±{MAX_COORDINATE}")));
+        }
+        Ok(Point3::new(x, y, z))
+    })
 }
 
 #[cfg(test)]
@@ -77,15 +81,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one(&read_file("examples", DAY)), Some(40));
+        assert_eq!(part_one(&read_file("examples", DAY)).ok(), Some(40));
         #[cfg(not(feature = "ci"))]
-        assert_eq!(part_one(&read_file("inputs", DAY)), Some(46398));
+        assert_eq!(part_one(&read_file("inputs", DAY)).ok(), Some(46398));
     }
 
     #[test]
     fn test_part_two() {
-        assert_eq!(part_two(&read_file("examples", DAY)), Some(25272));
+        assert_eq!(part_two(&read_file("examples", DAY)).ok(), Some(25272));
         #[cfg(not(feature = "ci"))]
-        assert_eq!(part_two(&read_file("inputs", DAY)), Some(8141888143));
+        assert_eq!(part_two(&read_file("inputs", DAY)).ok(), Some(8141888143));
     }
 }
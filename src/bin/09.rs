@@ -1,3 +1,6 @@
+use std::error::Error;
+
+use advent_of_code::parse;
 use advent_of_code::point::Point2;
 
 use itertools::Itertools;
@@ -5,8 +8,8 @@ use sscanf::scanf;
 
 advent_of_code::solution!(9);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let tiles = parse(input);
+pub fn part_one(input: &str) -> Result<u64, Box<dyn Error>> {
+    let tiles = parse(input)?;
 
     tiles
         .iter()
@@ -18,11 +21,12 @@ pub fn part_one(input: &str) -> Option<u64> {
             },
         )
         .max()
+        .ok_or_else(|| "need at least two red tiles".into())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut tiles = parse(input);
-    tiles.push(tiles[0]); // wrap around
+pub fn part_two(input: &str) -> Result<u64, Box<dyn Error>> {
+    let mut tiles = parse(input)?;
+    tiles.push(*tiles.first().ok_or("no red tiles")?); // wrap around
 
     tiles
         .iter()
@@ -36,6 +40,7 @@ pub fn part_two(input: &str) -> Option<u64> {
             }
         })
         .max()
+        .ok_or_else(|| "need at least two red tiles".into())
 }
 
 fn area_rect(p: &Point, q: &Point) -> u64 {
@@ -44,12 +49,33 @@ fn area_rect(p: &Point, q: &Point) -> u64 {
     (dx * dy) as u64
 }
 
-fn is_inside(polygon: &Vec<Point>, rect: &Rect) -> bool {
+fn is_inside(polygon: &[Point], rect: &Rect) -> bool {
     polygon
         .iter()
         .zip(polygon.iter().skip(1))
         .filter_map(|(p, q)| Segment::new(*p, *q))
         .all(|line| !line.intersects(rect))
+        && contains_center(polygon, rect)
+}
+
+/// Whether the center of `rect` lies inside `polygon` or on its boundary. When no
+/// edge cuts through the rectangle, this tells whether all of it is inside, e.g.
+/// for a rectangle spanning the notch of an L.
+fn contains_center(polygon: &[Point], rect: &Rect) -> bool {
+    // doubled coordinates, so that the center is a lattice point
+    let (cx, cy) = (rect.xmin + rect.xmax, rect.ymin + rect.ymax);
+    let mut inside = false;
+    for (p, q) in polygon.iter().zip(polygon.iter().skip(1)) {
+        let (xmin, xmax) = (2 * p.x.min(q.x), 2 * p.x.max(q.x));
+        let (ymin, ymax) = (2 * p.y.min(q.y), 2 * p.y.max(q.y));
+        if xmin <= cx && cx <= xmax && ymin <= cy && cy <= ymax {
+            return true;
+        }
+        if xmin == xmax && xmin > cx && ymin <= cy && cy < ymax {
+            inside = !inside;
+        }
+    }
+    inside
 }
 
 struct Rect {
@@ -117,32 +143,141 @@ impl Segment {
 
 type Point = Point2<i64>;
 
-fn parse(input: &str) -> Vec<Point> {
-    input
-        .lines()
-        .flat_map(|line| {
-            let (x, y) = scanf!(line, "{},{}", i64, i64).ok()?;
-            Some(Point2::new(x, y))
-        })
-        .collect()
+/// Keeps areas from overflowing.
+const MAX_COORDINATE: u64 = 1 << 30;
+
+fn parse(input: &str) -> parse::Result<Vec<Point>> {
+    parse::lines(input, |line| {
+        let (x, y) = line.scan(|s| scanf!(s, "{},{}", i64, i64))?;
+        if [x, y].iter().any(|c| c.unsigned_abs() > MAX_COORDINATE) {
+            return Err(line.error(format!("coordinates must be within ±{MAX_COORDINATE}")));
+        }
+        Ok(Point2::new(x, y))
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::{Differential, Gen};
     use advent_of_code::template::read_file;
+    use std::collections::HashSet;
+
+    /// An x-monotone polygon given as columns of a width, a bottom and a height.
+    fn generate(g: &mut Gen) -> Vec<(i64, i64, i64)> {
+        g.vec(|g| {
+            (
+                g.range(0..=4) as i64,
+                g.range(0..=8) as i64,
+                g.range(0..=8) as i64,
+            )
+        })
+    }
+
+    /// Walks along the tops of the columns and back along their bottoms. Each
+    /// column is clamped to overlap the previous one so that the loop never
+    /// touches itself. Coordinates are doubled: like in the puzzle input, no
+    /// two edges run right next to each other without a tile in between.
+    fn render(columns: &Vec<(i64, i64, i64)>) -> String {
+        let mut x = 0;
+        let mut spans: Vec<(i64, i64, i64, i64)> = vec![];
+        for &(width, bottom, height) in columns {
+            let (bottom, top) = match spans.last() {
+                Some(&(_, _, prev_bottom, prev_top)) => {
+                    let bottom = bottom.min(prev_top - 1);
+                    (bottom, (bottom + height + 1).max(prev_bottom + 1))
+                }
+                None => (bottom, bottom + height + 1),
+            };
+            spans.push((x, x + width + 1, bottom, top));
+            x += width + 1;
+        }
+
+        let mut corners = vec![];
+        for &(x0, x1, _, top) in &spans {
+            corners.extend([(x0, top), (x1, top)]);
+        }
+        for &(x0, x1, bottom, _) in spans.iter().rev() {
+            corners.extend([(x1, bottom), (x0, bottom)]);
+        }
+        corners.dedup();
+        if corners.len() > 1 && corners.first() == corners.last() {
+            corners.pop();
+        }
+
+        // drop corners in the middle of a straight edge
+        let n = corners.len();
+        let turns = (0..n).filter(|&i| {
+            let (p, q, r) = (corners[(i + n - 1) % n], corners[i], corners[(i + 1) % n]);
+            !(p.0 == q.0 && q.0 == r.0 || p.1 == q.1 && q.1 == r.1)
+        });
+        turns
+            .map(|i| format!("{},{}", 2 * corners[i].0, 2 * corners[i].1))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Fills in every tile of the loop and checks each rectangle tile by tile.
+    fn naive_part_two(input: &str) -> Option<u64> {
+        let red = parse(input).ok()?;
+        let edges: Vec<(Point, Point)> = red
+            .iter()
+            .zip(red.iter().cycle().skip(1))
+            .map(|(p, q)| (*p, *q))
+            .collect();
+
+        let on_edge = |t: Point| {
+            edges.iter().any(|(p, q)| {
+                p.x.min(q.x) <= t.x
+                    && t.x <= p.x.max(q.x)
+                    && p.y.min(q.y) <= t.y
+                    && t.y <= p.y.max(q.y)
+            })
+        };
+        let crossings = |t: Point| {
+            edges
+                .iter()
+                .filter(|(p, q)| {
+                    p.x == q.x && p.x > t.x && p.y.min(q.y) <= t.y && t.y < p.y.max(q.y)
+                })
+                .count()
+        };
+
+        let (xmax, ymax) = red
+            .iter()
+            .fold((0, 0), |(x, y), p| (x.max(p.x), y.max(p.y)));
+        let green: HashSet<Point> = (0..=xmax)
+            .flat_map(|x| (0..=ymax).map(move |y| Point2::new(x, y)))
+            .filter(|&t| on_edge(t) || crossings(t) % 2 == 1)
+            .collect();
+
+        red.iter()
+            .flat_map(|p| red.iter().map(move |q| (p, q)))
+            .filter(|(p, q)| {
+                let rect = Rect::new(**p, **q);
+                (rect.xmin..=rect.xmax)
+                    .all(|x| (rect.ymin..=rect.ymax).all(|y| green.contains(&Point2::new(x, y))))
+            })
+            .map(|(p, q)| area_rect(p, q))
+            .max()
+    }
 
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one(&read_file("examples", DAY)), Some(50));
+        assert_eq!(part_one(&read_file("examples", DAY)).ok(), Some(50));
         #[cfg(not(feature = "ci"))]
-        assert_eq!(part_one(&read_file("inputs", DAY)), Some(4748769124));
+        assert_eq!(part_one(&read_file("inputs", DAY)).ok(), Some(4748769124));
     }
 
     #[test]
     fn test_part_two() {
-        assert_eq!(part_two(&read_file("examples", DAY)), Some(24));
+        assert_eq!(part_two(&read_file("examples", DAY)).ok(), Some(24));
         #[cfg(not(feature = "ci"))]
-        assert_eq!(part_two(&read_file("inputs", DAY)), Some(1525991432));
+        assert_eq!(part_two(&read_file("inputs", DAY)).ok(), Some(1525991432));
+    }
+
+    #[test]
+    fn test_against_reference() {
+        Differential::new(generate, render).check(naive_part_two, |input| part_two(input).ok());
     }
 }
@@ -1,93 +1,89 @@
-use good_lp::*;
-use good_lp::solvers::lpsolve::Verbosity;
-use itertools::Itertools;
+use advent_of_code::gf2::{BitMatrix, BitVec};
+use advent_of_code::good_lp::solvers::lpsolve::Verbosity;
+use advent_of_code::good_lp::*;
 advent_of_code::solution!(10);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    Some(
-        parse(input)
-            .into_iter()
-            .map(|(goal, banks, _)| {
-                for set in banks.into_iter().powerset() {
-                    let presses = set.len() as u64;
-                    if set.into_iter().fold(0, |acc, b| acc ^ b) == goal {
-                        return presses;
-                    }
-                }
-                0
-            })
-            .sum(),
-    )
+    // a machine whose lights can't be reached has no answer, rather than 0
+    parse(input)
+        .into_iter()
+        .map(|(goal, banks, _)| {
+            let width = goal.len();
+            let buttons = BitMatrix::from_columns(
+                width,
+                banks
+                    .iter()
+                    .map(|bank| BitVec::from_indices(width, bank.iter().copied())),
+            );
+            let presses = buttons.min_weight_solution(&goal)?;
+            Some(presses.count_ones() as u64)
+        })
+        .sum()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    Some(
-        parse(input)
-            .into_iter()
-            .map(|(_, banks, joltage)| {
-                let mut vars = variables!();
-                let presses: Vec<Variable> = banks
-                    .iter()
-                    .map(|_| vars.add(variable().min(0).integer()))
-                    .collect();
+pub fn part_two(input: &str) -> Result<u64, ResolutionError> {
+    parse(input)
+        .into_iter()
+        .map(|(_, banks, joltage)| {
+            let mut vars = variables!();
+            let presses: Vec<Variable> = banks
+                .iter()
+                .map(|_| vars.add(variable().min(0).integer()))
+                .collect();
 
-                let width = joltage.len() - 1;
-                let solution = vars
-                    .minimise(presses.iter().sum::<Expression>())
-                    .using(default_solver)
-                    .set_verbose(Verbosity::Important)
-                    .with_all(joltage.iter().enumerate().map(|(i, jolt)| {
-                        let sum = banks
-                            .iter()
-                            .zip(presses.iter())
-                            .flat_map(|(bank, press)| {
-                                if ((1 << (width - i)) & bank) != 0 {
-                                    Some(*press)
-                                } else {
-                                    None
-                                }
-                            })
-                            .sum::<Expression>();
-                        constraint!(sum == *jolt)
-                    }))
-                    .solve()
-                    .unwrap();
+            let solution = vars
+                .minimise(presses.iter().sum::<Expression>())
+                .using(default_solver)
+                .set_verbose(Verbosity::Important)
+                .with_all(joltage.iter().enumerate().map(|(i, jolt)| {
+                    let sum = banks
+                        .iter()
+                        .zip(presses.iter())
+                        .flat_map(|(bank, press)| {
+                            if bank.contains(&i) {
+                                Some(*press)
+                            } else {
+                                None
+                            }
+                        })
+                        .sum::<Expression>();
+                    constraint!(sum == *jolt)
+                }))
+                .solve()?;
 
-                presses.into_iter().map(|p| solution.value(p)).sum::<f64>() as u64
-            })
-            .sum::<u64>(),
-    )
+            Ok(presses.into_iter().map(|p| solution.value(p)).sum::<f64>() as u64)
+        })
+        .sum()
 }
 
-fn parse(input: &str) -> Vec<(u16, Vec<u16>, Vec<u16>)> {
-    input.lines().flat_map(|line| parse_line(line)).collect()
+fn parse(input: &str) -> Vec<(BitVec, Vec<Vec<usize>>, Vec<u16>)> {
+    input.lines().flat_map(parse_line).collect()
 }
 
-// Represent button banks as bitmasks
+// Represent indicators as a bit vector and button banks as the indices they toggle
 //
-// [.##.] (___3) (_1_3) (__2_) (__23) (0_2_) (01__) {3,5,4,7}
-//  0110   ___1   _101   __10   __11   1010   1100
-fn parse_line(line: &str) -> Option<(u16, Vec<u16>, Vec<u16>)> {
-    let (indicators, rest) = line[1..].split_once("] ")?;
+// [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+//  0110   3   1,3   2   2,3   0,2   0,1
+fn parse_line(line: &str) -> Option<(BitVec, Vec<Vec<usize>>, Vec<u16>)> {
+    let (indicators, rest) = line.strip_prefix('[')?.split_once("] ")?;
     let (banks, rest) = rest.split_once(" {")?;
 
-    let goal: u16 = indicators.chars().fold(0, |acc, c| {
-        let bit = if c == '#' { 1 } else { 0 };
-        2 * acc + bit
-    });
+    let goal: BitVec = indicators.chars().map(|c| c == '#').collect();
 
-    let width = indicators.len() - 1;
-    let banks: Vec<u16> = banks
+    let banks: Vec<Vec<usize>> = banks
         .split_whitespace()
         .map(|bank| {
-            let bank = &bank[1..bank.len() - 1];
-            bank.split(",")
-                .flat_map(|n| n.parse::<u32>().ok())
-                .fold(0, |acc, n| acc + 2u16.pow(width as u32 - n))
+            let bank = bank.strip_prefix('(')?.strip_suffix(')')?;
+            let bank: Vec<usize> = bank
+                .split(",")
+                .flat_map(|n| n.parse::<usize>().ok())
+                .collect();
+            bank.iter().all(|&i| i < goal.len()).then_some(bank)
         })
-        .collect();
+        .collect::<Option<_>>()?;
 
-    let joltage: Vec<u16> = rest[..rest.len() - 1]
+    let joltage: Vec<u16> = rest
+        .strip_suffix('}')?
         .split(",")
         .flat_map(|n| n.parse::<u16>().ok())
         .collect();
@@ -107,10 +103,27 @@ mod tests {
         assert_eq!(part_one(&read_file("inputs", DAY)), Some(401));
     }
 
+    #[test]
+    fn test_part_one_unsolvable() {
+        // light 1 isn't wired to any button
+        assert_eq!(part_one("[.#] (0) {1,0}"), None);
+        assert_eq!(part_one("[.#] (0) {1,0}\n[#] (0) {1}"), None);
+    }
+
+    #[test]
+    fn test_part_one_too_many_buttons() {
+        // found by fuzzing: 70 buttons panicked in the solver and 40 never finished
+        for banks in [40, 70] {
+            let input = format!("[#] {}{{1}}", "(0) ".repeat(banks));
+            assert_eq!(part_one(&input), None);
+            let _ = part_two(&input);
+        }
+    }
+
     #[test]
     fn test_part_two() {
-        assert_eq!(part_two(&read_file("examples", DAY)), Some(33));
+        assert_eq!(part_two(&read_file("examples", DAY)).ok(), Some(33));
         #[cfg(not(feature = "ci"))]
-        assert_eq!(part_two(&read_file("inputs", DAY)), Some(15017));
+        assert_eq!(part_two(&read_file("inputs", DAY)).ok(), Some(15017));
     }
 }
@@ -1,71 +1,45 @@
+use advent_of_code::memo::Memo;
 use std::collections::HashMap;
 
 advent_of_code::solution!(11);
 
 pub fn part_one(input: &str) -> Option<u64> {
     let graph = parse(input);
-    Some(count_paths(&graph, "you", [], "out") as u64)
+    Some(count_paths(&graph, "you", [], "out")? as u64)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
     let graph = parse(input);
-    Some(count_paths(&graph, "svr", ["fft", "dac"], "out") as u64)
+    Some(count_paths(&graph, "svr", ["fft", "dac"], "out")? as u64)
 }
 
+/// The number of paths from `src` to `dst` through all `waypoints`, or `None`
+/// if there are infinitely many because a path runs into a cycle, or too many
+/// to count.
 fn count_paths<'input, const N: usize>(
     graph: &Graph<'input>,
-    src: &str,
+    src: &'input str,
     waypoints: [&str; N],
     dst: &str,
-) -> usize {
-    return dfs(
-        &mut HashMap::new(),
-        graph,
-        src,
-        waypoints.map(|w| (w, false)),
-        dst,
-    );
-
-    fn dfs<'node, 'input: 'node, 'waypoint, const N: usize>(
-        memo: &mut Memo<'node, 'waypoint, N>,
-        graph: &Graph<'input>,
-        node: &'node str,
-        waypoints: [(&'waypoint str, bool); N],
-        dst: &str,
-    ) -> usize {
-        if node == dst {
-            return if waypoints.iter().all(|(_, seen)| *seen) {
-                1
-            } else {
-                0
+) -> Option<usize> {
+    // keyed by the node and which waypoints have been seen before it
+    let mut paths = Memo::new(
+        |paths: &mut dyn FnMut((&'input str, [bool; N])) -> Option<usize>, (node, seen)| {
+            if node == dst {
+                return Some(seen.iter().all(|&seen| seen) as usize);
+            }
+            let Some(neighbors) = graph.get(node) else {
+                return Some(0);
             };
-        }
-
-        let key = (node, waypoints);
-        if let Some(&count) = memo.get(&key) {
-            return count;
-        }
-
-        let Some(neighbors) = graph.get(node) else {
-            return 0;
-        };
-
-        let count = neighbors
-            .iter()
-            .map(|neighbor| {
-                dfs(
-                    memo,
-                    graph,
-                    neighbor,
-                    waypoints.map(|(w, seen)| (w, seen || w == node)),
-                    dst,
-                )
+            let seen = std::array::from_fn(|i| seen[i] || waypoints[i] == node);
+            neighbors.iter().try_fold(0usize, |count, &neighbor| {
+                count.checked_add(paths((neighbor, seen))?)
             })
-            .sum();
-
-        memo.insert(key, count);
-        count
-    }
+        },
+    )
+    // still being visited, so the path runs into a cycle
+    .on_cycle(None);
+    paths.get((src, [false; N]))
 }
 
 fn parse(input: &str) -> Graph<'_> {
@@ -78,7 +52,6 @@ fn parse(input: &str) -> Graph<'_> {
         .collect()
 }
 
-type Memo<'node, 'waypoint, const N: usize> = HashMap<(&'node str, [(&'waypoint str, bool); N]), usize>;
 type Graph<'input> = HashMap<&'input str, Vec<&'input str>>;
 
 #[cfg(test)]
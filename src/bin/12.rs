@@ -1,39 +1,44 @@
+use advent_of_code::polyomino::{Polyomino, can_pack};
 use sscanf::scanf;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, 1);
 
 pub fn part_one(input: &str) -> Option<u64> {
+    let (shapes, regions) = parse(input)?;
+    // a region too large to decide leaves the answer unknown
+    regions.iter().try_fold(0, |fitting, (w, h, counts)| {
+        Some(fitting + can_pack(*w, *h, &shapes, counts)? as u64)
+    })
+}
+
+type Region = (usize, usize, Vec<usize>);
+
+fn parse(input: &str) -> Option<(Vec<Polyomino>, Vec<Region>)> {
     let chunks: Vec<&str> = input.split("\n\n").collect();
     let (regions, shapes) = chunks.split_last()?;
 
-    let shapes: Vec<usize> = shapes
-        .into_iter()
-        .map(|shape| shape.chars().filter(|&c| c == '#').count())
-        .collect();
+    let shapes: Vec<Polyomino> = shapes
+        .iter()
+        .map(|shape| {
+            let (_, picture) = shape.split_once(":\n")?;
+            picture.parse().ok()
+        })
+        .collect::<Option<_>>()?;
 
-    Some(
-        regions
-            .lines()
-            .flat_map(|line| {
-                let (dim, counts) = line.split_once(": ")?;
-                let (w, h) = scanf!(dim, "{}x{}", usize, usize).ok()?;
-                let area = counts
-                    .split_whitespace()
-                    .enumerate()
-                    .filter_map(|(i, n)| {
-                        let n = n.parse::<usize>().ok()?;
-                        Some(n * shapes[i])
-                    })
-                    .sum::<usize>();
-                Some(area <= w * h)
-            })
-            .filter(|ok| *ok)
-            .count() as u64,
-    )
-}
+    let regions = regions
+        .lines()
+        .flat_map(|line| {
+            let (dim, counts) = line.split_once(": ")?;
+            let (w, h) = scanf!(dim, "{}x{}", usize, usize).ok()?;
+            let counts: Vec<usize> = counts
+                .split_whitespace()
+                .flat_map(|n| n.parse().ok())
+                .collect();
+            (counts.len() == shapes.len()).then_some((w, h, counts))
+        })
+        .collect();
 
-pub fn part_two(_input: &str) -> Option<u64> {
-    Some(0)
+    Some((shapes, regions))
 }
 
 #[cfg(test)]
@@ -43,12 +48,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
+        assert_eq!(part_one(&read_file("examples", DAY)), Some(2));
         #[cfg(not(feature = "ci"))]
         assert_eq!(part_one(&read_file("inputs", DAY)), Some(422));
     }
 
     #[test]
-    fn test_part_two() {
-        assert_eq!(part_two(&read_file("examples", DAY)), Some(0));
+    fn test_part_one_huge_region() {
+        let example = read_file("examples", DAY);
+        let input = format!("{example}\n100000x100000: 0 0 0 0 1200000000 0");
+        assert_eq!(part_one(&input), None);
+        let input = format!("{example}\n10000000000x10000000000: 0 0 0 0 1 0");
+        assert_eq!(part_one(&input), None);
     }
 }
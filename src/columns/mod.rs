@@ -0,0 +1,120 @@
+//! Fixed-width text laid out in side-by-side blocks, like
+//!
+//! ```text
+//! 123 328  51 64
+//!  45 64  387 23
+//!   6 98  215 314
+//! ```
+//!
+//! where each block is separated from the next by a column that is blank on
+//! every line. Lines may be ragged: anything past the end of a line counts as
+//! blank, so trailing whitespace does not need to line up.
+
+/// Splits the lines into blocks separated by all-blank columns, from left to right.
+pub fn blocks<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<Block> {
+    let grid: Vec<Vec<char>> = lines.into_iter().map(|l| l.chars().collect()).collect();
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    let at = |row: &[char], x: usize| row.get(x).copied().unwrap_or(' ');
+    let is_blank = |x: usize| grid.iter().all(|row| at(row, x).is_whitespace());
+
+    let mut blocks = vec![];
+    let mut x = 0;
+    while x < width {
+        if is_blank(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && !is_blank(x) {
+            x += 1;
+        }
+        blocks.push(Block {
+            start,
+            cells: grid
+                .iter()
+                .map(|row| (start..x).map(|i| at(row, i)).collect())
+                .collect(),
+        });
+    }
+    blocks
+}
+
+/// A rectangular block of text cut out of the surrounding lines, padded with
+/// spaces where a line was too short.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Block {
+    start: usize,
+    cells: Vec<Vec<char>>,
+}
+
+impl Block {
+    /// The column at which the block starts in the original lines.
+    #[inline]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.cells.first().map_or(0, Vec::len)
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The character at column `x` of row `y`, both relative to the block.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        self.cells.get(y)?.get(x).copied()
+    }
+
+    /// The block read row by row, top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = String> + '_ {
+        self.cells.iter().map(|row| row.iter().collect())
+    }
+
+    /// The block read column by column, left to right, each column read top
+    /// to bottom. Use `.rev()` to read the columns right to left.
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = String> + '_ {
+        (0..self.width()).map(|x| self.cells.iter().map(|row| row[x]).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_blank_columns() {
+        let lines = [
+            "123 328  51 64 ",
+            " 45 64  387 23",
+            "  6 98  215 314",
+            "*   +   *   +",
+        ];
+        let blocks = blocks(lines);
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(
+            blocks.iter().map(Block::start).collect::<Vec<_>>(),
+            vec![0, 4, 8, 12]
+        );
+        assert_eq!(
+            blocks[0].rows().collect::<Vec<_>>(),
+            vec!["123", " 45", "  6", "*  "]
+        );
+        assert_eq!(blocks[3].width(), 3);
+        assert_eq!(blocks[3].rows().last().unwrap(), "+  ");
+        assert_eq!(
+            blocks[3].columns().rev().collect::<Vec<_>>(),
+            vec!["  4 ", "431 ", "623+"]
+        );
+    }
+
+    #[test]
+    fn handles_empty_input() {
+        assert!(blocks([]).is_empty());
+        assert!(blocks(["   ", ""]).is_empty());
+    }
+}
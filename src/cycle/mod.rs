@@ -0,0 +1,290 @@
+//! Finding where a sequence of states starts repeating, to skip ahead to the
+//! state after a huge number of steps.
+//!
+//! [`floyd`] and [`brent`] only keep a couple of states around, but step the
+//! sequence more than once and never return if it doesn't cycle. [`detect`]
+//! and [`detect_by`] remember every state instead, so that the state at any
+//! step can be looked up afterwards, which is what [`state_at`] and
+//! [`value_at`] do.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence `x0, f(x0), f(f(x0)), ...` starts repeating.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Cycle {
+    /// The first step whose state is repeated later on.
+    pub start: usize,
+    /// How many steps it takes to come back, 1 for a fixed point.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as `step`.
+    ///
+    /// ```
+    /// # use advent_of_code::cycle::Cycle;
+    /// let cycle = Cycle { start: 3, length: 4 };
+    /// assert_eq!(cycle.equivalent(2), 2);
+    /// assert_eq!(cycle.equivalent(1_000_000_001), 5);
+    /// ```
+    #[inline]
+    pub fn equivalent(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+}
+
+/// Floyd's tortoise and hare. Never returns if the sequence doesn't cycle.
+pub fn floyd<S: Clone + Eq>(initial: S, next: impl Fn(&S) -> S) -> Cycle {
+    let mut tortoise = next(&initial);
+    let mut hare = next(&tortoise);
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&next(&hare));
+    }
+
+    // the hare is now a multiple of the cycle length ahead of the tortoise
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = next(&tortoise);
+    while tortoise != hare {
+        hare = next(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Brent's algorithm, which steps the sequence fewer times than
+/// [`floyd`]. Never returns if the sequence doesn't cycle.
+pub fn brent<S: Clone + Eq>(initial: S, next: impl Fn(&S) -> S) -> Cycle {
+    // find the length by teleporting the tortoise to the hare at powers of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = next(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = next(&hare);
+        length += 1;
+    }
+
+    // then walk two pointers `length` apart until they meet
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = next(&hare);
+    }
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Steps until a state repeats. Returns the cycle and every state up to the
+/// repeat, so the state after `n` steps is `states[cycle.equivalent(n)]`.
+pub fn detect<S: Clone + Eq + Hash>(initial: S, next: impl FnMut(&S) -> S) -> (Cycle, Vec<S>) {
+    detect_by(initial, next, S::clone)
+}
+
+/// Like [`detect`], but only compares the part of each state given by `key`,
+/// e.g. everything but a counter that keeps growing.
+pub fn detect_by<S, K: Eq + Hash>(
+    initial: S,
+    next: impl FnMut(&S) -> S,
+    key: impl FnMut(&S) -> K,
+) -> (Cycle, Vec<S>) {
+    let (cycle, states) = run(initial, next, key, usize::MAX);
+    (cycle.expect("the sequence to repeat"), states)
+}
+
+/// The state after `n` steps, skipping whole cycles once the states start
+/// repeating.
+///
+/// ```
+/// # use advent_of_code::cycle::state_at;
+/// assert_eq!(state_at(1u64, |x| x * 3 % 7, 1_000_000_000), 4);
+/// ```
+pub fn state_at<S: Clone + Eq + Hash>(initial: S, next: impl FnMut(&S) -> S, n: usize) -> S {
+    let (cycle, mut states) = run(initial, next, S::clone, n);
+    let step = cycle.map_or(n, |cycle| cycle.equivalent(n));
+    states.swap_remove(step)
+}
+
+/// The `value` of the state after `n` steps, where states repeat according to
+/// `key` but the value may grow by the same amount on every cycle, like the
+/// height of a tower while its top keeps the same shape.
+pub fn value_at<S, K: Eq + Hash>(
+    initial: S,
+    next: impl FnMut(&S) -> S,
+    key: impl FnMut(&S) -> K,
+    mut value: impl FnMut(&S) -> i64,
+    n: usize,
+) -> i64 {
+    let (cycle, states) = run(initial, next, key, n);
+    let Some(cycle) = cycle else {
+        return value(&states[n]);
+    };
+    let cycles = ((n - cycle.start) / cycle.length) as i64;
+    let growth = value(&states[cycle.start + cycle.length]) - value(&states[cycle.start]);
+    value(&states[cycle.equivalent(n)]) + cycles * growth
+}
+
+/// Steps until a key repeats or `limit` steps have been taken. Also returns
+/// the states, including the first repeated one.
+fn run<S, K: Eq + Hash>(
+    initial: S,
+    mut next: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+    limit: usize,
+) -> (Option<Cycle>, Vec<S>) {
+    let mut seen = HashMap::new();
+    let mut states = vec![initial];
+    loop {
+        let step = states.len() - 1;
+        let state = &states[step];
+        if let Some(start) = seen.insert(key(state), step) {
+            let cycle = Cycle {
+                start,
+                length: step - start,
+            };
+            return (Some(cycle), states);
+        }
+        if step == limit {
+            return (None, states);
+        }
+        states.push(next(state));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential::forall;
+
+    /// The cycle of `x -> (a * x * x + b) % m` found by stepping and remembering.
+    fn naive(x: u64, a: u64, b: u64, m: u64) -> Cycle {
+        let mut seen = vec![];
+        let mut x = x % m;
+        loop {
+            if let Some(start) = seen.iter().position(|&y| y == x) {
+                return Cycle {
+                    start,
+                    length: seen.len() - start,
+                };
+            }
+            seen.push(x);
+            x = (a * x * x + b) % m;
+        }
+    }
+
+    #[test]
+    fn detectors_agree() {
+        forall(
+            |g| {
+                (
+                    g.range(0..=1000),
+                    g.range(0..=50),
+                    g.range(0..=50),
+                    g.range(1..=500),
+                )
+            },
+            |&(x, a, b, m)| {
+                let f = |&x: &u64| (a * x * x + b) % m;
+                let expected = naive(x, a, b, m);
+                floyd(x % m, f) == expected
+                    && brent(x % m, f) == expected
+                    && detect(x % m, f).0 == expected
+            },
+        );
+    }
+
+    #[test]
+    fn fixed_points_have_length_one() {
+        assert_eq!(
+            floyd(5, |&x: &u32| x.max(9)),
+            Cycle {
+                start: 1,
+                length: 1
+            }
+        );
+        assert_eq!(
+            brent(9, |&x: &u32| x.max(9)),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+        let (cycle, states) = detect(0, |&x: &u32| (x + 1).min(3));
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 3,
+                length: 1
+            }
+        );
+        assert_eq!(states, [0, 1, 2, 3, 3]);
+    }
+
+    #[test]
+    fn state_at_matches_stepping() {
+        forall(
+            |g| (g.range(0..=100), g.range(1..=100), g.range(0..=300)),
+            |&(x, m, n)| {
+                let f = |&x: &u64| (x * x + 1) % m;
+                let expected = (0..n).fold(x, |x, _| f(&x));
+                state_at(x, f, n as usize) == expected
+            },
+        );
+        // stops early without needing a cycle
+        let mut steps = 0;
+        let state = state_at(
+            0u64,
+            |&x| {
+                steps += 1;
+                x + 1
+            },
+            10,
+        );
+        assert_eq!((state, steps), (10, 10));
+    }
+
+    #[test]
+    fn value_at_extrapolates_growth() {
+        // a position going round a track of length `m`, and how far it has gone
+        forall(
+            |g| (g.range(1..=20), g.range(0..=2000), g.range(1..=5)),
+            |&(m, n, speed)| {
+                let step = |&(pos, dist): &(u64, i64)| ((pos + speed) % m, dist + speed as i64);
+                let value = value_at((0, 0), step, |&(pos, _)| pos, |&(_, dist)| dist, n as usize);
+                value == (n * speed) as i64
+            },
+        );
+        let (cycle, states) = detect_by((0u8, 0u64), |&(p, d)| ((p + 1) % 3, d + 1), |&(p, _)| p);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 0,
+                length: 3
+            }
+        );
+        assert_eq!(states.len(), 4);
+    }
+}
@@ -0,0 +1,160 @@
+/// A circular dial numbered `0..size` that turns one number per click.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Dial {
+    size: u64,
+    position: u64,
+}
+
+impl Dial {
+    /// Creates a dial of the given size pointing at `position` (taken modulo `size`).
+    pub const fn new(size: u64, position: u64) -> Self {
+        assert!(size > 0, "a dial needs at least one position");
+        Self {
+            size,
+            position: position % size,
+        }
+    }
+
+    #[inline]
+    pub const fn size(&self) -> u64 {
+        self.size
+    }
+
+    #[inline]
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Turns the dial by `clicks`, towards higher numbers if positive and
+    /// lower numbers if negative, and returns how many clicks left it
+    /// pointing at zero.
+    ///
+    /// Turning right from `p` by `k` visits `p + 1 ..= p + k`, which contains
+    /// `(p + k) / size` multiples of `size` since `p < size`. Turning left is
+    /// the same as turning right by `k` on the mirrored dial, where `p` sits
+    /// at `(size - p) % size`. Whole turns are split off first and the rest
+    /// is done in `u128`, so that huge rotations on huge dials cannot overflow.
+    pub const fn rotate(&mut self, clicks: i64) -> u64 {
+        let (turns, rest) = (
+            clicks.unsigned_abs() / self.size,
+            (clicks.unsigned_abs() % self.size) as u128,
+        );
+        let (size, position) = (self.size as u128, self.position as u128);
+        let (from, to) = if clicks >= 0 {
+            (position, position + rest)
+        } else {
+            ((size - position) % size, position + size - rest)
+        };
+        self.position = (to % size) as u64;
+        // `from + rest < 2 * size`, so this is 0 or 1
+        turns + ((from + rest) / size) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dial;
+    use crate::differential::{Gen, forall};
+
+    fn step_by_step(size: u64, position: u64, clicks: i64) -> (u64, u64) {
+        let (size, mut position) = (size as i64, position as i64);
+        let mut zeros = 0;
+        for _ in 0..clicks.unsigned_abs() {
+            position = (position + clicks.signum()).rem_euclid(size);
+            if position == 0 {
+                zeros += 1;
+            }
+        }
+        (zeros, position as u64)
+    }
+
+    #[test]
+    fn matches_step_by_step_simulation() {
+        for size in 1..=12 {
+            for start in 0..size {
+                for clicks in -50..=50 {
+                    let mut dial = Dial::new(size, start);
+                    let zeros = dial.rotate(clicks);
+                    assert_eq!(
+                        (zeros, dial.position()),
+                        step_by_step(size, start, clicks),
+                        "size {size}, start {start}, clicks {clicks}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn handles_huge_rotations() {
+        let mut dial = Dial::new(100, 50);
+        assert_eq!(dial.rotate(i64::MAX), (i64::MAX as u64 + 50) / 100);
+        let mut dial = Dial::new(100, 50);
+        assert_eq!(dial.rotate(i64::MIN), (i64::MIN.unsigned_abs() + 50) / 100);
+    }
+
+    #[test]
+    fn handles_huge_dials() {
+        let mut dial = Dial::new(u64::MAX, u64::MAX - 1);
+        assert_eq!(dial.rotate(i64::MAX), 1);
+        assert_eq!(dial.position(), i64::MAX as u64 - 1);
+        assert_eq!(dial.rotate(i64::MIN), 1);
+        assert_eq!(dial.position(), u64::MAX - 2);
+        assert_eq!(dial.rotate(2), 1);
+        assert_eq!(dial.position(), 0);
+        assert_eq!(dial.rotate(-1), 0);
+        assert_eq!(dial.position(), u64::MAX - 1);
+
+        let mut dial = Dial::new(u64::MAX, 0);
+        assert_eq!(dial.rotate(i64::MIN), 0);
+        assert_eq!(dial.position(), u64::MAX - i64::MIN.unsigned_abs());
+    }
+
+    /// `rotate` worked out in `i128`, counting the clicks that land on zero
+    /// directly: `p + j` for `j` in `1..=k`, or `p - j` when turning left.
+    fn reference(size: u64, position: u64, clicks: i64) -> (u64, u64) {
+        let (n, p, k) = (size as i128, position as i128, clicks as i128);
+        let zeros = if k >= 0 {
+            (p + k) / n
+        } else if p == 0 {
+            -k / n
+        } else if -k >= p {
+            1 + (-k - p) / n
+        } else {
+            0
+        };
+        (zeros as u64, (p + k).rem_euclid(n) as u64)
+    }
+
+    #[test]
+    fn matches_reference() {
+        forall(
+            |g| {
+                let size = match g.range(0..=2) {
+                    0 => g.range(1..=1000),
+                    1 => u64::MAX - g.range(0..=1000),
+                    _ => g.range(1..=u64::MAX),
+                };
+                let clicks = |g: &mut Gen| match g.range(0..=3) {
+                    0 => g.signed(-1000..=1000),
+                    1 => i64::MAX - g.signed(0..=1000),
+                    2 => i64::MIN + g.signed(0..=1000),
+                    _ => g.signed(i64::MIN..=i64::MAX),
+                };
+                (size, g.range(0..=u64::MAX), g.vec(clicks))
+            },
+            |(size, start, turns)| {
+                let mut dial = Dial::new((*size).max(1), *start);
+                turns.iter().all(|&clicks| {
+                    let expected = reference(dial.size(), dial.position(), clicks);
+                    dial.rotate(clicks) == expected.0 && dial.position() == expected.1
+                })
+            },
+        );
+    }
+
+    #[test]
+    fn wraps_start_position() {
+        assert_eq!(Dial::new(100, 250).position(), 50);
+    }
+}
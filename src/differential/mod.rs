@@ -0,0 +1,392 @@
+//! Differential testing of a solution against a naive reference implementation.
+//!
+//! A day describes its puzzle input as a plain value (say, a list of ranges),
+//! how to generate random values and how to render one as puzzle text. Both
+//! implementations run on every rendered input. When they disagree, the value
+//! is shrunk, quickcheck-style, to a smallest input that still shows the
+//! difference, which then gets reported together with both answers.
+//!
+//! ```
+//! # use advent_of_code::differential::Differential;
+//! fn naive(input: &str) -> u64 {
+//!     input.lines().map(|line| line.parse::<u64>().unwrap()).sum()
+//! }
+//!
+//! fn fast(input: &str) -> u64 {
+//!     input.lines().flat_map(str::parse::<u64>).sum()
+//! }
+//!
+//! Differential::new(
+//!     |g| g.vec(|g| g.range(0..=1000)),
+//!     |numbers: &Vec<u64>| numbers.iter().map(u64::to_string).collect::<Vec<_>>().join("\n"),
+//! )
+//! .check(naive, fast);
+//! ```
+//!
+//! Runs are reproducible: the seed is fixed unless `AOC_CHECK_SEED` is set,
+//! and `AOC_CHECK_CASES` overrides the number of generated inputs.
+
+use std::env;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::panic::{self, AssertUnwindSafe};
+
+const DEFAULT_SEED: u64 = 20251201;
+const DEFAULT_CASES: usize = 100;
+
+/// The largest size passed to generators, reached by the last case.
+const MAX_SIZE: usize = 20;
+
+/// Gives up shrinking after this many smaller failing inputs have been found.
+const MAX_SHRINKS: usize = 1000;
+
+/// Source of random values for generators.
+///
+/// `size` grows from 1 to 20 over the cases of a run, so the first inputs
+/// are small and later ones get larger.
+pub struct Gen {
+    state: u64,
+    size: usize,
+}
+
+impl Gen {
+    pub fn new(seed: u64, size: usize) -> Self {
+        Self { state: seed, size }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The next random number, using splitmix64.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (lo, hi) = range.into_inner();
+        assert!(lo <= hi, "empty range {lo}..={hi}");
+        match (hi - lo).checked_add(1) {
+            Some(len) => lo + self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    /// A signed number in `range`, which must not be empty.
+    pub fn signed(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (lo, hi) = range.into_inner();
+        assert!(lo <= hi, "empty range {lo}..={hi}");
+        lo.wrapping_add(self.range(0..=hi.abs_diff(lo)) as i64)
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Between zero and `size` values made by `f`.
+    pub fn vec<T>(&mut self, mut f: impl FnMut(&mut Gen) -> T) -> Vec<T> {
+        let len = self.range(0..=self.size as u64);
+        (0..len).map(|_| f(self)).collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Values that can be made simpler while keeping their shape.
+pub trait Shrink: Sized {
+    /// Simpler variants of `self`, most aggressive first.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! impl_shrink_unsigned {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut smaller = vec![0, self / 2, self - self.min(&1)];
+                smaller.dedup();
+                smaller.retain(|x| x < self);
+                smaller
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_shrink_signed {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut smaller = vec![0, self / 2, self - self.signum()];
+                smaller.extend(self.checked_neg().filter(|x| *x > 0));
+                smaller.dedup();
+                smaller.retain(|x| x.unsigned_abs() < self.unsigned_abs() || x > self);
+                smaller
+            }
+        }
+    )*};
+}
+
+impl_shrink_unsigned!(u8, u16, u32, u64, usize);
+impl_shrink_signed!(i8, i16, i32, i64, isize);
+
+impl Shrink for bool {
+    fn shrink(&self) -> Vec<Self> {
+        if *self { vec![false] } else { vec![] }
+    }
+}
+
+/// Drops the back or front half, then single elements, then shrinks single elements.
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut smaller = vec![];
+        let half = self.len() / 2;
+        if half > 0 {
+            smaller.push(self[..half].to_vec());
+            smaller.push(self[half..].to_vec());
+        }
+        for i in 0..self.len() {
+            let mut v = self.clone();
+            v.remove(i);
+            smaller.push(v);
+        }
+        for (i, x) in self.iter().enumerate() {
+            smaller.extend(x.shrink().into_iter().map(|x| {
+                let mut v = self.clone();
+                v[i] = x;
+                v
+            }));
+        }
+        smaller
+    }
+}
+
+macro_rules! impl_shrink_tuple {
+    ($(($($name:ident $i:tt),+)),*) => {$(
+        impl<$($name: Shrink + Clone),+> Shrink for ($($name,)+) {
+            fn shrink(&self) -> Vec<Self> {
+                let mut smaller = vec![];
+                $(
+                    smaller.extend(self.$i.shrink().into_iter().map(|x| {
+                        let mut t = self.clone();
+                        t.$i = x;
+                        t
+                    }));
+                )+
+                smaller
+            }
+        }
+    )*};
+}
+
+impl_shrink_tuple!(
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3)
+);
+
+/* -------------------------------------------------------------------------- */
+
+/// A property comparing two implementations on generated puzzle inputs.
+pub struct Differential<T> {
+    generate: Box<dyn Fn(&mut Gen) -> T>,
+    render: Box<dyn Fn(&T) -> String>,
+    seed: u64,
+    cases: usize,
+}
+
+impl<T: Shrink + Clone + Debug> Differential<T> {
+    /// `render` must turn every value, including shrunk ones, into a valid input.
+    pub fn new(
+        generate: impl Fn(&mut Gen) -> T + 'static,
+        render: impl Fn(&T) -> String + 'static,
+    ) -> Self {
+        Self {
+            generate: Box::new(generate),
+            render: Box::new(render),
+            seed: env_or("AOC_CHECK_SEED", DEFAULT_SEED),
+            cases: env_or("AOC_CHECK_CASES", DEFAULT_CASES),
+        }
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Runs `reference` and `optimized` on every generated input. Panics with a
+    /// shrunk counterexample if they disagree or if one of them panics.
+    pub fn check<R: PartialEq + Debug>(
+        &self,
+        reference: impl Fn(&str) -> R,
+        optimized: impl Fn(&str) -> R,
+    ) {
+        let differs = |value: &T| {
+            let input = (self.render)(value);
+            let expected = catch(|| reference(&input));
+            let actual = catch(|| optimized(&input));
+            (expected != actual).then_some((input, expected, actual))
+        };
+
+        for case in 0..self.cases {
+            let size = 1 + case * MAX_SIZE / self.cases.max(1);
+            let mut g = Gen::new(self.seed.wrapping_add(case as u64), size);
+            let value = (self.generate)(&mut g);
+
+            let Some(failure) = differs(&value) else {
+                continue;
+            };
+
+            let (value, (input, expected, actual), shrinks) = shrink(value, failure, differs);
+            panic!(
+                "reference and optimized implementation disagree \
+                (seed {}, case {case}, {shrinks} shrinks)\n\
+                value: {value:?}\n\
+                input:\n{input}\n\
+                reference: {}\n\
+                optimized: {}",
+                self.seed,
+                describe(&expected),
+                describe(&actual),
+            );
+        }
+    }
+}
+
+/// Checks that `property` holds for generated values, for testing library code
+/// the same way. Panics with a shrunk counterexample if the property is false
+/// or panics.
+///
+/// ```
+/// # use advent_of_code::differential::forall;
+/// forall(|g| (g.range(0..=100), g.range(0..=100)), |&(a, b)| a + b >= a.max(b));
+/// ```
+pub fn forall<T: Shrink + Clone + Debug>(
+    generate: impl Fn(&mut Gen) -> T,
+    property: impl Fn(&T) -> bool,
+) {
+    let seed = env_or("AOC_CHECK_SEED", DEFAULT_SEED);
+    let cases = env_or("AOC_CHECK_CASES", DEFAULT_CASES);
+    let fails = |value: &T| (catch(|| property(value)) != Some(true)).then_some(());
+
+    for case in 0..cases {
+        let size = 1 + case * MAX_SIZE / cases.max(1);
+        let mut g = Gen::new(seed.wrapping_add(case as u64), size);
+        let value = generate(&mut g);
+
+        if fails(&value).is_some() {
+            let (value, _, shrinks) = shrink(value, (), fails);
+            panic!(
+                "property does not hold (seed {seed}, case {case}, {shrinks} shrinks) for {value:?}"
+            );
+        }
+    }
+}
+
+/// Repeatedly replaces `value` with the first simpler variant that still fails.
+fn shrink<T: Shrink, F>(
+    mut value: T,
+    mut failure: F,
+    fails: impl Fn(&T) -> Option<F>,
+) -> (T, F, usize) {
+    let mut shrinks = 0;
+    'outer: while shrinks < MAX_SHRINKS {
+        for smaller in value.shrink() {
+            if let Some(f) = fails(&smaller) {
+                value = smaller;
+                failure = f;
+                shrinks += 1;
+                continue 'outer;
+            }
+        }
+        break;
+    }
+    (value, failure, shrinks)
+}
+
+/// The result of `f`, or `None` if it panicked.
+fn catch<R>(f: impl FnOnce() -> R) -> Option<R> {
+    panic::catch_unwind(AssertUnwindSafe(f)).ok()
+}
+
+fn describe<R: Debug>(result: &Option<R>) -> String {
+    match result {
+        Some(result) => format!("{result:?}"),
+        None => "panicked".into(),
+    }
+}
+
+pub(crate) fn env_or<V: std::str::FromStr>(key: &str, default: V) -> V {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinks_numbers_towards_zero() {
+        assert_eq!(10u64.shrink(), vec![0, 5, 9]);
+        assert_eq!(1u8.shrink(), vec![0]);
+        assert!(0u32.shrink().is_empty());
+        assert_eq!((-6i64).shrink(), vec![0, -3, -5, 6]);
+    }
+
+    #[test]
+    fn shrinks_to_a_minimal_counterexample() {
+        let fails = |v: &Vec<u64>| v.iter().any(|&x| x >= 10).then_some(());
+        let (value, _, _) = shrink(vec![3, 18, 1, 7], (), fails);
+        assert_eq!(value, vec![10]);
+    }
+
+    #[test]
+    fn passes_when_implementations_agree() {
+        Differential::new(
+            |g| g.vec(|g| g.range(1..=9)),
+            |v: &Vec<u64>| format!("{v:?}"),
+        )
+        .cases(50)
+        .check(str::len, |s| s.chars().count());
+    }
+
+    #[test]
+    fn forall_passes_when_the_property_holds() {
+        forall(
+            |g| (g.range(0..=1000), g.signed(-1000..=1000)),
+            |&(a, b)| a * a >= a && b.abs() <= 1000,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "for [100]")]
+    fn forall_shrinks_counterexamples() {
+        forall(
+            |g| g.vec(|g| g.range(0..=1000)),
+            |v| v.iter().sum::<u64>() < 100,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "value: [100]")]
+    fn reports_the_shrunk_value() {
+        Differential::new(
+            |g| g.vec(|g| g.range(0..=1000)),
+            |v: &Vec<u64>| v.iter().map(u64::to_string).collect::<Vec<_>>().join("\n"),
+        )
+        .check(
+            |s| s.lines().all(|n| n.parse::<u64>().unwrap() < 100),
+            |_| true,
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! Working with the digits of a number in an arbitrary base without going
+//! through strings.
+
+/// The digits of `n` in `base`, most significant first. Zero has the single digit `0`.
+///
+/// ```
+/// # use advent_of_code::digits::digits;
+/// assert_eq!(digits(1234, 10).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+/// assert_eq!(digits(1234, 10).rev().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+/// assert_eq!(digits(6, 2).collect::<Vec<_>>(), vec![1, 1, 0]);
+/// ```
+pub fn digits(n: u64, base: u64) -> Digits {
+    Digits {
+        n,
+        base,
+        pow: leading_power(n, base),
+    }
+}
+
+/// Iterator returned by [`digits`].
+#[derive(Debug, Clone)]
+pub struct Digits {
+    /// The digits that have not been yielded yet, as a number.
+    n: u64,
+    base: u64,
+    /// The weight of the leading digit of `n`, or zero once exhausted.
+    pow: u64,
+}
+
+impl Iterator for Digits {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pow == 0 {
+            return None;
+        }
+        let d = self.n / self.pow;
+        self.n %= self.pow;
+        self.pow /= self.base;
+        Some(d)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Digits {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.pow == 0 {
+            return None;
+        }
+        let d = self.n % self.base;
+        self.n /= self.base;
+        self.pow /= self.base;
+        Some(d)
+    }
+}
+
+impl ExactSizeIterator for Digits {
+    fn len(&self) -> usize {
+        if self.pow == 0 {
+            0
+        } else {
+            self.pow.ilog(self.base) as usize + 1
+        }
+    }
+}
+
+/// `base` raised to one less than the number of digits in `n`.
+fn leading_power(n: u64, base: u64) -> u64 {
+    assert!(base >= 2, "base must be at least 2");
+    base.pow(count(n, base) - 1)
+}
+
+/// The number of digits in `n` when written in `base`.
+pub fn count(n: u64, base: u64) -> u32 {
+    assert!(base >= 2, "base must be at least 2");
+    n.checked_ilog(base).map_or(1, |x| x + 1)
+}
+
+/// Builds a number from its digits in `base`, most significant first.
+pub fn from_digits(digits: impl IntoIterator<Item = u64>, base: u64) -> u64 {
+    digits.into_iter().fold(0, |acc, d| {
+        debug_assert!(d < base, "digit {d} out of range for base {base}");
+        acc * base + d
+    })
+}
+
+/// Writes the digits of `b` after the digits of `a`, or `None` if the result
+/// doesn't fit in a `u64`.
+///
+/// ```
+/// # use advent_of_code::digits::concat;
+/// assert_eq!(concat(12, 345, 10), Some(12345));
+/// assert_eq!(concat(12, 0, 10), Some(120));
+/// assert_eq!(concat(u64::MAX / 2, 99, 10), None);
+/// ```
+pub fn concat(a: u64, b: u64, base: u64) -> Option<u64> {
+    a.checked_mul(base.checked_pow(count(b, base))?)?
+        .checked_add(b)
+}
+
+/// Writes the digits of `n` out `times` times in a row, or `None` if the
+/// result doesn't fit in a `u64`.
+///
+/// ```
+/// # use advent_of_code::digits::repeat;
+/// assert_eq!(repeat(12, 3, 10), Some(121212));
+/// ```
+pub fn repeat(n: u64, times: u32, base: u64) -> Option<u64> {
+    let shift = base.checked_pow(count(n, base))?;
+    (0..times).try_fold(0u64, |acc, _| acc.checked_mul(shift)?.checked_add(n))
+}
+
+/// Whether the digits of `n` read the same in both directions.
+pub fn is_palindrome(n: u64, base: u64) -> bool {
+    let mut digits = digits(n, base);
+    while let (Some(a), Some(b)) = (digits.next(), digits.next_back()) {
+        if a != b {
+            return false;
+        }
+    }
+    true
+}
+
+/// Whether the digits of `n` are some shorter block of digits repeated at least twice.
+pub fn is_periodic(n: u64, base: u64) -> bool {
+    let len = count(n, base);
+    (1..len)
+        .filter(|d| len.is_multiple_of(*d))
+        .any(|d| repeat(n / base.pow(len - d), len / d, base) == Some(n))
+}
+
+/// The largest number that can be made by picking `k` of the given digits
+/// without reordering them, or `None` if there are fewer than `k` digits.
+///
+/// Walks the digits once with a stack: each digit pops the smaller digits
+/// before it while enough digits remain to still pick `k`, so the stack always
+/// starts with the best choice for the prefix seen so far.
+///
+/// ```
+/// # use advent_of_code::digits::largest_subsequence;
+/// assert_eq!(largest_subsequence(&[8, 1, 1, 9, 1, 2], 3, 10), Some(912));
+/// ```
+pub fn largest_subsequence(digits: &[u64], k: usize, base: u64) -> Option<u64> {
+    let mut drops = digits.len().checked_sub(k)?;
+    let mut stack = Vec::with_capacity(digits.len());
+    for &d in digits {
+        while drops > 0 && stack.last().is_some_and(|&top| top < d) {
+            stack.pop();
+            drops -= 1;
+        }
+        stack.push(d);
+    }
+    stack.truncate(k);
+    Some(from_digits(stack, base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iterates_in_any_base() {
+        assert_eq!(digits(0, 10).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(digits(255, 16).collect::<Vec<_>>(), vec![15, 15]);
+        assert_eq!(digits(u64::MAX, 10).len(), 20);
+        assert_eq!(digits(u64::MAX, 2).len(), 64);
+        let mut d = digits(12345, 10);
+        assert_eq!((d.next(), d.next_back(), d.len()), (Some(1), Some(5), 3));
+        assert_eq!(d.collect::<Vec<_>>(), vec![2, 3, 4]);
+        for n in [0, 7, 10, 99, 1000, 123456789, u64::MAX] {
+            assert_eq!(from_digits(digits(n, 10), 10), n);
+            assert_eq!(from_digits(digits(n, 3), 3), n);
+            assert_eq!(count(n, 10) as usize, n.to_string().len());
+        }
+    }
+
+    #[test]
+    fn palindromes_and_periods() {
+        assert!(is_palindrome(0, 10));
+        assert!(is_palindrome(12321, 10));
+        assert!(!is_palindrome(1232, 10));
+        assert!(is_palindrome(0b1001, 2));
+        assert!(is_periodic(1212, 10));
+        assert!(is_periodic(111, 10));
+        assert!(is_periodic(123123123, 10));
+        assert!(!is_periodic(7, 10));
+        assert!(!is_periodic(1213, 10));
+        assert!(is_periodic(1010, 10));
+        assert!(!is_periodic(1001, 10));
+    }
+
+    #[test]
+    fn concat_and_repeat_overflow() {
+        assert_eq!(concat(12, 345, 10), Some(12345));
+        assert_eq!(concat(0, 7, 2), Some(7));
+        assert_eq!(concat(u64::MAX / 2, 99, 10), None);
+        assert_eq!(concat(1, u64::MAX, 10), None);
+        assert_eq!(
+            concat(1, u64::MAX / 10, 10),
+            Some(10u64.pow(19) + u64::MAX / 10)
+        );
+        assert_eq!(repeat(12, 0, 10), Some(0));
+        assert_eq!(repeat(7, 19, 10), Some(7_777_777_777_777_777_777));
+        assert_eq!(repeat(7, 20, 10), None);
+        assert_eq!(repeat(1, 64, 2), Some(u64::MAX));
+        assert_eq!(repeat(1, 65, 2), None);
+    }
+
+    #[test]
+    fn largest_subsequence_matches_brute_force() {
+        let digits = [2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 7, 8];
+        for k in 0..=digits.len() {
+            let best = (0u32..1 << digits.len())
+                .filter(|mask| mask.count_ones() as usize == k)
+                .map(|mask| {
+                    from_digits(
+                        (0..digits.len())
+                            .filter(|i| mask >> i & 1 == 1)
+                            .map(|i| digits[i]),
+                        10,
+                    )
+                })
+                .max();
+            assert_eq!(largest_subsequence(&digits, k, 10), best, "k = {k}");
+        }
+        assert_eq!(largest_subsequence(&digits, digits.len() + 1, 10), None);
+    }
+}
@@ -0,0 +1,247 @@
+//! A small mutation-based fuzzer that checks a solution never panics, whatever
+//! text it is given.
+//!
+//! The corpus is seeded with the examples of a day. Every iteration takes an
+//! example (or the previous mutant) and applies a few random edits: deleting,
+//! duplicating or swapping lines, truncating, deleting or replacing characters
+//! and inserting tokens that tend to break parsers, like numbers that don't fit,
+//! a minus sign, separators or non-ASCII characters. When the target panics,
+//! the input is shrunk by deleting characters for as long as it keeps panicking.
+//!
+//! This finds the same kind of bugs as the libFuzzer targets in `fuzz/`, just
+//! without coverage guidance, so it can run as a plain test on stable Rust.
+//! Stack overflows and aborts take down the whole process rather than being
+//! reported, and a target that hangs makes the run hang.
+
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::differential::{Gen, env_or};
+use crate::template::Day;
+
+const DEFAULT_SEED: u64 = 20251201;
+const DEFAULT_ITERATIONS: usize = 1000;
+
+/// Gives up shrinking a panicking input after this many attempts.
+const MAX_SHRINK_RUNS: usize = 5000;
+
+/// Inserted in addition to the characters already found in the examples.
+#[rustfmt::skip]
+const TOKENS: &[&str] = &[
+    "\n", "\n\n", " ", ",", ":", "-", "0", "1", "-1", "255", "65536", "4294967296",
+    "18446744073709551616", "9223372036854775807", "-9223372036854775808",
+    "é", "€", "(", ")", "[", "]", "{", "}", "#", ".", "x",
+];
+
+/// Feeds mutated examples of a day into a target function.
+pub struct Fuzzer {
+    name: String,
+    seeds: Vec<Vec<char>>,
+    seed: u64,
+    iterations: usize,
+}
+
+impl Fuzzer {
+    /// Seeds the corpus with `data/examples/<day>.txt` and every `data/examples/<day>-<n>.txt`.
+    pub fn new(day: Day) -> Self {
+        let mut paths: Vec<_> = fs::read_dir("data/examples")
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                name == format!("{day}.txt")
+                    || name.starts_with(&format!("{day}-")) && name.ends_with(".txt")
+            })
+            .collect();
+        paths.sort();
+
+        let examples = paths.iter().flat_map(fs::read_to_string);
+        Self::from_examples(format!("day {day}"), examples)
+    }
+
+    /// Seeds the corpus with the given inputs. The empty input is always included.
+    pub fn from_examples(
+        name: impl Into<String>,
+        examples: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Self {
+        let mut seeds: Vec<Vec<char>> = vec![vec![]];
+        seeds.extend(examples.into_iter().map(|e| e.as_ref().chars().collect()));
+        Self {
+            name: name.into(),
+            seeds,
+            seed: env_or("AOC_FUZZ_SEED", DEFAULT_SEED),
+            iterations: env_or("AOC_FUZZ_ITERATIONS", DEFAULT_ITERATIONS),
+        }
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Runs `target` on every mutated input. Panics with a shrunk input if
+    /// `target` panics.
+    pub fn run(&self, target: impl Fn(&str)) {
+        let alphabet = self.alphabet();
+        let mut g = Gen::new(self.seed, 0);
+        let mut input = vec![];
+
+        for iteration in 0..self.iterations {
+            if iteration == 0 || g.bool() {
+                input = self.seeds[pick(&mut g, self.seeds.len())].clone();
+            }
+            for _ in 0..=g.range(0..=3) {
+                self.mutate(&mut g, &mut input, &alphabet);
+            }
+
+            if let Err(message) = catch(&target, &input) {
+                let (input, message) = shrink(&target, input, message);
+                panic!(
+                    "{} panicked on a mutated example (seed {}, iteration {iteration}): \
+                    {message}\ninput: {:?}",
+                    self.name,
+                    self.seed,
+                    input.iter().collect::<String>(),
+                );
+            }
+        }
+    }
+
+    /// Every distinct character of the examples, plus the extra tokens.
+    fn alphabet(&self) -> Vec<String> {
+        let mut chars: Vec<char> = self.seeds.iter().flatten().copied().collect();
+        chars.sort_unstable();
+        chars.dedup();
+        chars
+            .into_iter()
+            .map(String::from)
+            .chain(TOKENS.iter().map(|t| t.to_string()))
+            .collect()
+    }
+
+    fn mutate(&self, g: &mut Gen, input: &mut Vec<char>, alphabet: &[String]) {
+        let at = |g: &mut Gen, input: &Vec<char>| pick(g, input.len() + 1);
+        let op = g.range(0..=7);
+        match op {
+            // delete a run of characters
+            0 => {
+                let i = at(g, input);
+                let len = pick(g, 8).min(input.len() - i);
+                input.drain(i..i + len);
+            }
+            // replace a character
+            1 if !input.is_empty() => {
+                let i = pick(g, input.len());
+                let token = &alphabet[pick(g, alphabet.len())];
+                input.splice(i..=i, token.chars());
+            }
+            // truncate
+            2 => {
+                let i = at(g, input);
+                input.truncate(i);
+            }
+            // delete, duplicate or swap lines
+            3..=5 => {
+                let mut lines: Vec<Vec<char>> =
+                    input.split(|&c| c == '\n').map(<[char]>::to_vec).collect();
+                let i = pick(g, lines.len());
+                let j = pick(g, lines.len());
+                match op {
+                    3 => drop(lines.remove(i)),
+                    4 => lines.insert(j, lines[i].clone()),
+                    _ => lines.swap(i, j),
+                }
+                *input = lines.join(&'\n');
+            }
+            // splice in the tail of another seed
+            6 => {
+                let other = &self.seeds[pick(g, self.seeds.len())];
+                let (i, j) = (at(g, input), pick(g, other.len() + 1));
+                input.truncate(i);
+                input.extend_from_slice(&other[j..]);
+            }
+            // insert a token
+            _ => {
+                let i = at(g, input);
+                let token = &alphabet[pick(g, alphabet.len())];
+                input.splice(i..i, token.chars());
+            }
+        }
+    }
+}
+
+/// A random index below `len`, which must not be zero.
+fn pick(g: &mut Gen, len: usize) -> usize {
+    g.range(0..=len as u64 - 1) as usize
+}
+
+/// Deletes chunks of characters, halving the chunk size whenever no chunk can
+/// be deleted, for as long as `target` keeps panicking.
+fn shrink(target: impl Fn(&str), mut input: Vec<char>, mut message: String) -> (Vec<char>, String) {
+    let mut runs = 0;
+    let mut chunk = input.len().div_ceil(2).max(1);
+    while runs < MAX_SHRINK_RUNS {
+        let mut i = 0;
+        let mut deleted = false;
+        while i < input.len() && runs < MAX_SHRINK_RUNS {
+            let mut smaller = input.clone();
+            smaller.drain(i..(i + chunk).min(input.len()));
+            runs += 1;
+            match catch(&target, &smaller) {
+                Err(m) => {
+                    (input, message, deleted) = (smaller, m, true);
+                }
+                Ok(()) => i += chunk,
+            }
+        }
+        if !deleted {
+            if chunk == 1 {
+                break;
+            }
+            chunk = chunk.div_ceil(2);
+        }
+    }
+    (input, message)
+}
+
+/// Runs `target`, returning the panic message if it panics.
+fn catch(target: &impl Fn(&str), input: &[char]) -> Result<(), String> {
+    let input: String = input.iter().collect();
+    panic::catch_unwind(AssertUnwindSafe(|| target(&input))).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".into())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passes_when_the_target_never_panics() {
+        Fuzzer::from_examples("parse", ["1,2,3\n4,5\n"])
+            .iterations(500)
+            .run(|input| {
+                let _: Vec<Result<i64, _>> = input.split(',').map(str::parse).collect();
+            });
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a char boundary")]
+    fn reports_a_shrunk_input() {
+        Fuzzer::from_examples("slice", ["[1] [2]\n[3]\n"]).run(|input| {
+            for line in input.lines().filter(|line| !line.is_empty()) {
+                let _ = &line[1..];
+            }
+        });
+    }
+}
@@ -0,0 +1,413 @@
+use std::fmt::Display;
+use std::ops::{BitXor, BitXorAssign};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// The largest nullity [`BitMatrix::min_weight_solution`] searches, which
+/// bounds it to about a million steps.
+pub const MAX_NULLITY: usize = 20;
+
+/// A fixed-length vector over GF(2), packed into 64-bit words.
+///
+/// Bits past `len` in the last word are always kept clear so that equality,
+/// hashing and `count_ones` only ever see the meaningful bits.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct BitVec {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl BitVec {
+    pub fn zeros(len: usize) -> Self {
+        Self {
+            len,
+            words: vec![0; len.div_ceil(WORD_BITS)],
+        }
+    }
+
+    /// Creates a vector of length `len` with exactly the given bits set.
+    pub fn from_indices(len: usize, indices: impl IntoIterator<Item = usize>) -> Self {
+        let mut v = Self::zeros(len);
+        for i in indices {
+            v.set(i, true);
+        }
+        v
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "bit {i} out of range for length {}", self.len);
+        self.words[i / WORD_BITS] >> (i % WORD_BITS) & 1 == 1
+    }
+
+    #[inline]
+    pub fn set(&mut self, i: usize, bit: bool) {
+        assert!(i < self.len, "bit {i} out of range for length {}", self.len);
+        let mask = 1 << (i % WORD_BITS);
+        if bit {
+            self.words[i / WORD_BITS] |= mask;
+        } else {
+            self.words[i / WORD_BITS] &= !mask;
+        }
+    }
+
+    #[inline]
+    pub fn toggle(&mut self, i: usize) {
+        assert!(i < self.len, "bit {i} out of range for length {}", self.len);
+        self.words[i / WORD_BITS] ^= 1 << (i % WORD_BITS);
+    }
+
+    /// The Hamming weight of the vector.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// Whether the two vectors have no set bits in common.
+    pub fn is_disjoint(&self, other: &BitVec) -> bool {
+        assert_eq!(self.len, other.len, "length mismatch");
+        self.words.iter().zip(&other.words).all(|(a, b)| a & b == 0)
+    }
+
+    /// Indices of the set bits in ascending order.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * WORD_BITS + bit)
+            })
+        })
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let bits: Vec<bool> = iter.into_iter().collect();
+        Self::from_indices(
+            bits.len(),
+            bits.iter().enumerate().filter(|(_, b)| **b).map(|(i, _)| i),
+        )
+    }
+}
+
+impl BitXorAssign<&BitVec> for BitVec {
+    fn bitxor_assign(&mut self, other: &BitVec) {
+        assert_eq!(self.len, other.len, "length mismatch");
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a ^= b;
+        }
+    }
+}
+
+impl BitXor<&BitVec> for BitVec {
+    type Output = Self;
+
+    fn bitxor(mut self, other: &BitVec) -> Self {
+        self ^= other;
+        self
+    }
+}
+
+impl Display for BitVec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (0..self.len).try_for_each(|i| write!(f, "{}", if self.get(i) { '1' } else { '0' }))
+    }
+}
+
+/// A dense matrix over GF(2), stored as a list of packed rows.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct BitMatrix {
+    cols: usize,
+    rows: Vec<BitVec>,
+}
+
+impl BitMatrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            cols,
+            rows: vec![BitVec::zeros(cols); rows],
+        }
+    }
+
+    /// Builds a matrix from its rows, which must all have length `cols`.
+    pub fn from_rows(cols: usize, rows: impl IntoIterator<Item = BitVec>) -> Self {
+        let rows: Vec<BitVec> = rows.into_iter().collect();
+        assert!(rows.iter().all(|r| r.len() == cols), "row length mismatch");
+        Self { cols, rows }
+    }
+
+    /// Builds a matrix from its columns, which must all have length `rows`.
+    ///
+    /// This is the natural shape for button/light puzzles: column `j` is the
+    /// set of lights toggled by button `j`.
+    pub fn from_columns(rows: usize, columns: impl IntoIterator<Item = BitVec>) -> Self {
+        let columns: Vec<BitVec> = columns.into_iter().collect();
+        let mut m = Self::zeros(rows, columns.len());
+        for (j, column) in columns.iter().enumerate() {
+            assert_eq!(column.len(), rows, "column length mismatch");
+            for i in column.ones() {
+                m.rows[i].set(j, true);
+            }
+        }
+        m
+    }
+
+    #[inline]
+    pub fn nrows(&self) -> usize {
+        self.rows.len()
+    }
+
+    #[inline]
+    pub fn ncols(&self) -> usize {
+        self.cols
+    }
+
+    #[inline]
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.rows[row].get(col)
+    }
+
+    #[inline]
+    pub fn set(&mut self, row: usize, col: usize, bit: bool) {
+        self.rows[row].set(col, bit);
+    }
+
+    #[inline]
+    pub fn row(&self, row: usize) -> &BitVec {
+        &self.rows[row]
+    }
+
+    pub fn rank(&self) -> usize {
+        self.eliminate(None).pivots.len()
+    }
+
+    /// Some `x` with `self * x == b`, or `None` if the system is inconsistent.
+    ///
+    /// Free variables are set to zero, so this is not necessarily the
+    /// solution of minimum weight; see [`BitMatrix::min_weight_solution`].
+    pub fn solve(&self, b: &BitVec) -> Option<BitVec> {
+        self.eliminate(Some(b)).particular(self.cols)
+    }
+
+    /// A basis for the set of `x` with `self * x == 0`.
+    pub fn nullspace(&self) -> Vec<BitVec> {
+        self.eliminate(None).nullspace(self.cols)
+    }
+
+    /// The solution of `self * x == b` with the fewest set bits, or `None`
+    /// if the system is inconsistent or its nullity is above [`MAX_NULLITY`].
+    ///
+    /// Every solution is the particular solution plus some combination of
+    /// the nullspace basis, so this walks all `2^nullity` combinations in
+    /// Gray code order, touching one basis vector per step. The search is
+    /// exponential in the nullity rather than in the number of columns,
+    /// which keeps it cheap when the system is close to fully determined.
+    /// Finding the lightest solution is NP-hard in general, so systems with
+    /// more freedom than that are turned down instead of searched.
+    pub fn min_weight_solution(&self, b: &BitVec) -> Option<BitVec> {
+        let echelon = self.eliminate(Some(b));
+        let mut x = echelon.particular(self.cols)?;
+        let basis = echelon.nullspace(self.cols);
+        if basis.len() > MAX_NULLITY {
+            return None;
+        }
+
+        let mut best = x.clone();
+        for step in 1u64..1 << basis.len() {
+            x ^= &basis[step.trailing_zeros() as usize];
+            if x.count_ones() < best.count_ones() {
+                best.clone_from(&x);
+            }
+        }
+        Some(best)
+    }
+
+    /// Reduces a copy of the matrix to reduced row echelon form, applying the
+    /// same row operations to `b` if given.
+    fn eliminate(&self, b: Option<&BitVec>) -> Echelon {
+        if let Some(b) = b {
+            assert_eq!(b.len(), self.nrows(), "right-hand side length mismatch");
+        }
+
+        let mut rows = self.rows.clone();
+        let mut rhs = b.cloned().unwrap_or_else(|| BitVec::zeros(self.nrows()));
+        let mut pivots = vec![];
+
+        for col in 0..self.cols {
+            let r = pivots.len();
+            if r == rows.len() {
+                break;
+            }
+            let Some(p) = (r..rows.len()).find(|&i| rows[i].get(col)) else {
+                continue;
+            };
+            rows.swap(r, p);
+            let (a, b) = (rhs.get(r), rhs.get(p));
+            rhs.set(r, b);
+            rhs.set(p, a);
+
+            let (pivot, pivot_rhs) = (rows[r].clone(), rhs.get(r));
+            for (i, row) in rows.iter_mut().enumerate() {
+                if i != r && row.get(col) {
+                    *row ^= &pivot;
+                    if pivot_rhs {
+                        rhs.toggle(i);
+                    }
+                }
+            }
+            pivots.push(col);
+        }
+
+        Echelon { rows, rhs, pivots }
+    }
+}
+
+impl Display for BitMatrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.rows.iter().try_for_each(|row| writeln!(f, "{row}"))
+    }
+}
+
+/// A matrix in reduced row echelon form along with its transformed
+/// right-hand side. Row `i < pivots.len()` has its leading one in column
+/// `pivots[i]`; the remaining rows are zero.
+struct Echelon {
+    rows: Vec<BitVec>,
+    rhs: BitVec,
+    pivots: Vec<usize>,
+}
+
+impl Echelon {
+    fn particular(&self, cols: usize) -> Option<BitVec> {
+        if (self.pivots.len()..self.rows.len()).any(|i| self.rhs.get(i)) {
+            return None;
+        }
+        Some(BitVec::from_indices(
+            cols,
+            self.pivots
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| self.rhs.get(*i))
+                .map(|(_, col)| *col),
+        ))
+    }
+
+    fn nullspace(&self, cols: usize) -> Vec<BitVec> {
+        (0..cols)
+            .filter(|col| !self.pivots.contains(col))
+            .map(|free| {
+                let mut v = BitVec::zeros(cols);
+                v.set(free, true);
+                for (i, &pivot) in self.pivots.iter().enumerate() {
+                    if self.rows[i].get(free) {
+                        v.set(pivot, true);
+                    }
+                }
+                v
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mul(m: &BitMatrix, x: &BitVec) -> BitVec {
+        (0..m.nrows())
+            .map(|i| x.ones().filter(|&j| m.get(i, j)).count() % 2 == 1)
+            .collect()
+    }
+
+    #[test]
+    fn bitvec_spans_words() {
+        let mut v = BitVec::from_indices(130, [0, 63, 64, 129]);
+        assert_eq!(v.count_ones(), 4);
+        assert_eq!(v.ones().collect::<Vec<_>>(), vec![0, 63, 64, 129]);
+        v.toggle(64);
+        assert!(!v.get(64));
+        v ^= &BitVec::from_indices(130, [0, 63, 129]);
+        assert!(v.is_zero());
+    }
+
+    #[test]
+    fn rank_and_nullspace() {
+        let m = BitMatrix::from_rows(
+            4,
+            ["1100", "0110", "1010"].map(|row| row.chars().map(|c| c == '1').collect()),
+        );
+        assert_eq!(m.rank(), 2);
+        let basis = m.nullspace();
+        assert_eq!(basis.len(), 2);
+        for v in &basis {
+            assert!(!v.is_zero());
+            assert!(mul(&m, v).is_zero());
+        }
+    }
+
+    #[test]
+    fn inconsistent_system() {
+        let m = BitMatrix::from_rows(
+            2,
+            ["11", "11"].map(|row| row.chars().map(|c| c == '1').collect()),
+        );
+        assert_eq!(m.solve(&BitVec::from_indices(2, [0])), None);
+        assert!(m.solve(&BitVec::from_indices(2, [0, 1])).is_some());
+    }
+
+    #[test]
+    fn min_weight_solution_is_minimal() {
+        // [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) -- needs two presses
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let m = BitMatrix::from_columns(
+            4,
+            buttons.map(|b| BitVec::from_indices(4, b.iter().copied())),
+        );
+        let goal = BitVec::from_indices(4, [1, 2]);
+        let x = m.min_weight_solution(&goal).unwrap();
+        assert_eq!(mul(&m, &x), goal);
+        assert_eq!(x.count_ones(), 2);
+    }
+
+    #[test]
+    fn min_weight_solution_gives_up_on_large_nullity() {
+        // every column toggles the single light, so all but one are free
+        let m = |n| BitMatrix::from_columns(1, (0..n).map(|_| BitVec::from_indices(1, [0])));
+        let goal = BitVec::from_indices(1, [0]);
+        let x = m(MAX_NULLITY + 1).min_weight_solution(&goal).unwrap();
+        assert_eq!(x.count_ones(), 1);
+        assert_eq!(m(MAX_NULLITY + 2).min_weight_solution(&goal), None);
+        assert_eq!(m(100).min_weight_solution(&goal), None);
+    }
+
+    #[test]
+    fn wide_system() {
+        // toggling light i also toggles light i + 1, across a 100-light strip
+        let n = 100;
+        let m =
+            BitMatrix::from_columns(n, (0..n).map(|i| BitVec::from_indices(n, [i, (i + 1) % n])));
+        assert_eq!(m.rank(), n - 1);
+        let goal = BitVec::from_indices(n, [10, 90]);
+        let x = m.min_weight_solution(&goal).unwrap();
+        assert_eq!(mul(&m, &x), goal);
+        assert_eq!(x.count_ones(), 20);
+    }
+}
@@ -1,4 +1,20 @@
+pub mod automaton;
+pub mod columns;
+pub mod cycle;
+pub mod dial;
+pub mod differential;
+pub mod digits;
+pub mod fuzz;
+pub mod gf2;
+pub mod memo;
+pub mod parse;
+pub mod periodic;
 pub mod point;
+pub mod polyomino;
 pub mod template;
 
+// Solutions use the LP solver through here, so that the fuzz targets, which
+// compile them as modules of another crate, get it from the same place.
+pub use good_lp;
+
 // Use this file to add helper functions and additional modules.
@@ -1,4 +1,4 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, time, verify};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
@@ -7,7 +7,7 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{Day, InputSource, Selection};
     use std::process;
 
     pub enum AppArguments {
@@ -23,18 +23,30 @@ mod args {
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            selection: Selection,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            timeout: Option<u64>,
+            input: InputSource,
+            watch: bool,
         },
         All {
+            selection: Option<Selection>,
             release: bool,
+            timeout: Option<u64>,
+            jobs: usize,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            selection: Option<Selection>,
             store: bool,
+            timeout: Option<u64>,
+        },
+        Verify {
+            selection: Option<Selection>,
+            release: bool,
+            timeout: Option<u64>,
         },
         #[cfg(feature = "today")]
         Today,
@@ -44,17 +56,38 @@ mod args {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+            Some("all") => {
+                let release = args.contains("--release");
+                let timeout = args.opt_value_from_str("--timeout")?;
+                let jobs = args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1);
+
+                AppArguments::All {
+                    selection: args.opt_free_from_str()?,
+                    release,
+                    timeout,
+                    jobs,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let timeout = args.opt_value_from_str("--timeout")?;
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    selection: args.opt_free_from_str()?,
                     store,
+                    timeout,
+                }
+            }
+            Some("verify") => {
+                let release = args.contains("--release");
+                let timeout = args.opt_value_from_str("--timeout")?;
+
+                AppArguments::Verify {
+                    selection: args.opt_free_from_str()?,
+                    release,
+                    timeout,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -69,10 +102,17 @@ mod args {
                 overwrite: args.contains("--overwrite"),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                selection: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                timeout: args.opt_value_from_str("--timeout")?,
+                input: InputSource::from_flags(
+                    args.opt_value_from_str("--input")?,
+                    args.opt_value_from_str("--example-part")?,
+                    args.contains("--example"),
+                ),
+                watch: args.contains("--watch"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -102,8 +142,23 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                selection,
+                release,
+                timeout,
+                jobs,
+            } => all::handle(selection, release, timeout, jobs),
+            AppArguments::Time {
+                selection,
+                all,
+                store,
+                timeout,
+            } => time::handle(selection, all, store, timeout),
+            AppArguments::Verify {
+                selection,
+                release,
+                timeout,
+            } => verify::handle(selection, release, timeout),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
@@ -117,11 +172,14 @@ fn main() {
                 }
             }
             AppArguments::Solve {
-                day,
+                selection,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                timeout,
+                input,
+                watch,
+            } => solve::handle(selection, release, dhat, submit, timeout, &input, watch),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
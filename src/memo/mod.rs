@@ -0,0 +1,224 @@
+//! Memoizing recursive functions without threading a cache through every call.
+//!
+//! The function is a closure that gets a handle to call itself with:
+//!
+//! ```
+//! # use advent_of_code::memo::Memo;
+//! let mut fib = Memo::new(|fib: &mut dyn FnMut(u32) -> u64, n| {
+//!     if n < 2 { n as u64 } else { fib(n - 1) + fib(n - 2) }
+//! });
+//! assert_eq!(fib.get(90), 2880067194370816120);
+//! ```
+//!
+//! Keys can borrow from the input, like `(&str, usize)`, without spelling out
+//! any lifetimes. Each key is computed once, and a key that ends up depending
+//! on itself either panics or gets the value set with [`Memo::on_cycle`].
+//! Deep recursions can use [`Memo::get_iterative`], which keeps its own stack
+//! instead of the call stack.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+const CYCLE: &str = "memoized function depends on itself, see Memo::on_cycle";
+
+enum Entry<V> {
+    /// The key is being computed further up the stack.
+    Visiting,
+    Done(V),
+}
+
+/// A recursive function `f` together with the values it has computed.
+pub struct Memo<K, V, F> {
+    cache: HashMap<K, Entry<V>>,
+    f: F,
+    on_cycle: Option<V>,
+}
+
+impl<K, V, F> Memo<K, V, F>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn new(f: F) -> Self {
+        Self {
+            cache: HashMap::new(),
+            f,
+            on_cycle: None,
+        }
+    }
+
+    /// The value of a key that is looked up while it is still being computed,
+    /// instead of panicking, e.g. `None` when counting paths in a graph that
+    /// might have loops.
+    pub fn on_cycle(mut self, value: V) -> Self {
+        self.on_cycle = Some(value);
+        self
+    }
+
+    /// `f(key)`, computed with plain recursion.
+    pub fn get(&mut self, key: K) -> V {
+        let Self { cache, f, on_cycle } = self;
+        return go(cache, f, on_cycle, key);
+
+        fn go<K: Clone + Eq + Hash, V: Clone>(
+            cache: &mut HashMap<K, Entry<V>>,
+            f: &impl Fn(&mut dyn FnMut(K) -> V, K) -> V,
+            on_cycle: &Option<V>,
+            key: K,
+        ) -> V {
+            match cache.get(&key) {
+                Some(Entry::Done(value)) => return value.clone(),
+                Some(Entry::Visiting) => return on_cycle.clone().expect(CYCLE),
+                None => {}
+            }
+            cache.insert(key.clone(), Entry::Visiting);
+            let value = f(&mut |k| go(cache, f, on_cycle, k), key.clone());
+            cache.insert(key, Entry::Done(value.clone()));
+            value
+        }
+    }
+
+    /// `f(key)`, computed without recursing so that long chains of calls can't
+    /// overflow the stack.
+    ///
+    /// Whenever `f` asks for a key that isn't known yet it gets `V::default()`
+    /// back, that key is computed first, and then `f` is run again. So `f` has
+    /// to be pure, mustn't panic on default values, and runs once more for
+    /// every key it depends on.
+    pub fn get_iterative(&mut self, key: K) -> V
+    where
+        V: Default,
+    {
+        let Self { cache, f, on_cycle } = self;
+        match cache.get(&key) {
+            Some(Entry::Done(value)) => return value.clone(),
+            Some(Entry::Visiting) => return on_cycle.clone().expect(CYCLE),
+            None => {}
+        }
+
+        // every key on the stack depends on the one after it, just like the
+        // frames of the recursive version
+        cache.insert(key.clone(), Entry::Visiting);
+        let mut stack = vec![key.clone()];
+        while let Some(top) = stack.last().cloned() {
+            let mut missing = None;
+            let value = f(
+                &mut |k| match cache.get(&k) {
+                    Some(Entry::Done(value)) => value.clone(),
+                    Some(Entry::Visiting) => on_cycle.clone().expect(CYCLE),
+                    None => {
+                        missing.get_or_insert(k);
+                        V::default()
+                    }
+                },
+                top.clone(),
+            );
+            match missing {
+                Some(k) => {
+                    cache.insert(k.clone(), Entry::Visiting);
+                    stack.push(k);
+                }
+                None => {
+                    cache.insert(top, Entry::Done(value));
+                    stack.pop();
+                }
+            }
+        }
+
+        match cache.get(&key) {
+            Some(Entry::Done(value)) => value.clone(),
+            _ => unreachable!("the first key is finished last"),
+        }
+    }
+
+    /// Every key that has been computed, in no particular order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.cache
+            .iter()
+            .filter(|(_, entry)| matches!(entry, Entry::Done(_)))
+            .map(|(key, _)| key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::differential::forall;
+
+    #[test]
+    fn computes_each_key_once() {
+        let calls = Cell::new(0);
+        let mut fib = Memo::new(|fib: &mut dyn FnMut(u32) -> u128, n| {
+            calls.set(calls.get() + 1);
+            if n < 2 {
+                n as u128
+            } else {
+                fib(n - 1) + fib(n - 2)
+            }
+        });
+        assert_eq!(fib.get(150), 9969216677189303386214405760200);
+        assert_eq!(calls.get(), 151);
+        assert_eq!(fib.get(100), 354224848179261915075);
+        assert_eq!(calls.get(), 151);
+        assert_eq!(fib.keys().count(), 151);
+    }
+
+    #[test]
+    fn iterative_handles_deep_chains() {
+        let mut sum = Memo::new(
+            |sum: &mut dyn FnMut(u64) -> u64, n| {
+                if n == 0 { 0 } else { n + sum(n - 1) }
+            },
+        );
+        assert_eq!(sum.get_iterative(200_000), 200_000 * 200_001 / 2);
+    }
+
+    /// The number of paths from node 0 to the last node of a graph with edges
+    /// from every node to the nodes in `edges`, wrapping around. Computed both
+    /// recursively and iteratively.
+    fn paths(edges: &[Vec<usize>]) -> (Option<u64>, Option<u64>) {
+        let Some(last) = edges.len().checked_sub(1) else {
+            return (Some(0), Some(0));
+        };
+        let f = |paths: &mut dyn FnMut(usize) -> Option<u64>, node: usize| {
+            if node == last {
+                return Some(1);
+            }
+            edges[node]
+                .iter()
+                .map(|&next| next % edges.len())
+                .try_fold(0u64, |total, next| total.checked_add(paths(next)?))
+        };
+        (
+            Memo::new(f).on_cycle(None).get(0),
+            Memo::new(f).on_cycle(None).get_iterative(0),
+        )
+    }
+
+    #[test]
+    fn recursive_and_iterative_agree() {
+        forall(
+            |g| g.vec(|g| g.vec(|g| g.range(0..=20) as usize)),
+            |edges| {
+                let (recursive, iterative) = paths(edges);
+                recursive == iterative
+            },
+        );
+    }
+
+    #[test]
+    fn reports_cycles() {
+        assert_eq!(paths(&[vec![1, 3], vec![2], vec![1], vec![]]), (None, None));
+        assert_eq!(paths(&[vec![1, 2], vec![2], vec![]]), (Some(2), Some(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "depends on itself")]
+    fn panics_on_cycles_by_default() {
+        let mut forever = Memo::new(|f: &mut dyn FnMut(u8) -> u8, n| f((n + 1) % 3));
+        forever.get(0);
+    }
+}
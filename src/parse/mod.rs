@@ -0,0 +1,337 @@
+//! Parsing puzzle input without silently skipping lines that don't match.
+//!
+//! Everything is built around [`Span`], a slice of the input that remembers
+//! where it came from. Splitting a span into lines, sections or list items
+//! keeps track of line and column numbers, so any step can fail with a
+//! [`ParseError`] that points at the offending text.
+
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+/// Parses every line of the input with `f`, stopping at the first error.
+pub fn lines<'a, T>(input: &'a str, f: impl FnMut(Span<'a>) -> Result<T>) -> Result<Vec<T>> {
+    Span::new(input).lines().map(f).collect()
+}
+
+/// The blank-line separated sections of the input.
+pub fn sections(input: &str) -> Vec<Span<'_>> {
+    Span::new(input).sections()
+}
+
+/// A piece of the input together with the line and column (both starting at 1)
+/// where it begins.
+#[derive(Debug, Clone, Copy)]
+pub struct Span<'a> {
+    input: &'a str,
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Span<'a> {
+    /// A span covering the whole input.
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            text: input,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// An error pointing at the start of this span.
+    pub fn error(&self, reason: impl Display) -> ParseError {
+        let text = self.input.lines().nth(self.line - 1).unwrap_or_default();
+        ParseError {
+            line: self.line,
+            column: self.column,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// The part of this span starting at byte offset `start` with length `len`.
+    fn slice(&self, start: usize, len: usize) -> Self {
+        let before = &self.text[..start];
+        let (line, column) = match before.rfind('\n') {
+            None => (self.line, self.column + before.chars().count()),
+            Some(i) => (
+                self.line + before.matches('\n').count(),
+                1 + before[i + 1..].chars().count(),
+            ),
+        };
+        Self {
+            input: self.input,
+            text: &self.text[start..start + len],
+            line,
+            column,
+        }
+    }
+
+    /// The span of a substring that was borrowed from this span's text.
+    fn sub(&self, part: &'a str) -> Self {
+        let start = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        self.slice(start, part.len())
+    }
+
+    pub fn lines(self) -> impl Iterator<Item = Span<'a>> {
+        self.text.lines().map(move |line| self.sub(line))
+    }
+
+    /// Splits on runs of blank lines, dropping the blank lines themselves.
+    pub fn sections(self) -> Vec<Span<'a>> {
+        let mut sections = vec![];
+        let mut current: Option<(usize, usize)> = None;
+        for line in self.lines() {
+            let start = line.text.as_ptr() as usize - self.text.as_ptr() as usize;
+            if line.text.trim().is_empty() {
+                if let Some((s, e)) = current.take() {
+                    sections.push(self.slice(s, e - s));
+                }
+            } else {
+                let end = start + line.text.len();
+                current = Some((current.map_or(start, |(s, _)| s), end));
+            }
+        }
+        if let Some((s, e)) = current {
+            sections.push(self.slice(s, e - s));
+        }
+        sections
+    }
+
+    /// Splits on every occurrence of `sep`.
+    pub fn split(self, sep: &'a str) -> impl Iterator<Item = Span<'a>> {
+        self.text.split(sep).map(move |part| self.sub(part))
+    }
+
+    pub fn split_once(self, sep: &str) -> Result<(Span<'a>, Span<'a>)> {
+        let (a, b) = self
+            .text
+            .split_once(sep)
+            .ok_or_else(|| self.error(format!("expected {sep:?}")))?;
+        Ok((self.sub(a), self.sub(b)))
+    }
+
+    pub fn strip_prefix(self, prefix: &str) -> Result<Span<'a>> {
+        let rest = self
+            .text
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error(format!("expected {prefix:?}")))?;
+        Ok(self.sub(rest))
+    }
+
+    pub fn strip_suffix(self, suffix: &str) -> Result<Span<'a>> {
+        let rest = self.text.strip_suffix(suffix).ok_or_else(|| {
+            self.slice(self.text.len(), 0)
+                .error(format!("expected {suffix:?}"))
+        })?;
+        Ok(self.sub(rest))
+    }
+
+    pub fn trim(self) -> Span<'a> {
+        self.sub(self.text.trim())
+    }
+
+    /// Parses the whole span with [`FromStr`].
+    pub fn parse<T>(self) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .parse()
+            .map_err(|e| self.error(format!("invalid value {:?}: {e}", self.text)))
+    }
+
+    /// Parses the whole span with `f`, e.g. a `sscanf!` invocation.
+    pub fn scan<T, E: Display>(
+        self,
+        f: impl FnOnce(&'a str) -> std::result::Result<T, E>,
+    ) -> Result<T> {
+        f(self.text).map_err(|e| self.error(e))
+    }
+
+    /// Parses a list of values separated by `sep`, ignoring whitespace around each one.
+    pub fn list<T>(self, sep: &'a str) -> Result<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.split(sep).map(|item| item.trim().parse()).collect()
+    }
+
+    /// Every integer in the span, ignoring whatever separates them. A `-`
+    /// directly in front of a number makes it negative.
+    pub fn integers<T>(self) -> Result<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let bytes = self.text.as_bytes();
+        let mut numbers = vec![];
+        let mut i = 0;
+        while i < bytes.len() {
+            if !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+            let start = if i > 0 && bytes[i - 1] == b'-' {
+                i - 1
+            } else {
+                i
+            };
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            numbers.push(self.slice(start, i - start).parse()?);
+        }
+        Ok(numbers)
+    }
+
+    /// Parses a rectangular grid of characters, mapping each one with `f`.
+    /// Characters that `f` rejects and rows of the wrong length are errors.
+    pub fn grid<T>(self, mut f: impl FnMut(char) -> Option<T>) -> Result<Vec<Vec<T>>> {
+        let mut width = None;
+        self.lines()
+            .map(|line| {
+                let row = line
+                    .text
+                    .char_indices()
+                    .map(|(i, c)| {
+                        f(c).ok_or_else(|| {
+                            line.slice(i, c.len_utf8())
+                                .error(format!("unexpected character {c:?}"))
+                        })
+                    })
+                    .collect::<Result<Vec<T>>>()?;
+                match width {
+                    None => width = Some(row.len()),
+                    Some(w) if w != row.len() => {
+                        return Err(
+                            line.error(format!("expected {w} columns, found {}", row.len()))
+                        );
+                    }
+                    _ => {}
+                }
+                Ok(row)
+            })
+            .collect()
+    }
+}
+
+impl Display for Span<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing input, pointing at the line and
+/// column where things went wrong.
+#[derive(PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The full line of input the error occurred on.
+    pub text: String,
+    pub reason: String,
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.reason
+        )?;
+        writeln!(f, "    {}", self.text)?;
+        write!(f, "    {:>1$}", "^", self.column)
+    }
+}
+
+/// Shows the same message as [`Display`] so that `unwrap` and `expect` point
+/// at the bad input rather than dumping the struct.
+impl Debug for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_line_and_column() {
+        let input = "1,2,3\n4,x,6\n7,8,9";
+        let err = lines(input, |line| line.list::<u8>(",")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "4,x,6");
+        assert!(err.to_string().ends_with("\n    4,x,6\n      ^"));
+
+        let ok = lines(input.replace('x', "5").as_str(), |line| {
+            line.list::<u8>(",")
+        })
+        .unwrap();
+        assert_eq!(ok[1], vec![4, 5, 6]);
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "a\nb\n\n\nc\n  \nd\n";
+        let sections = sections(input);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].as_str(), "a\nb");
+        assert_eq!((sections[1].as_str(), sections[1].line()), ("c", 5));
+        assert_eq!((sections[2].as_str(), sections[2].line()), ("d", 7));
+        let err = sections[2].split_once(":").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (7, 1, "d"));
+    }
+
+    #[test]
+    fn extracts_integers() {
+        let span = Span::new("p=-3,14 v=2,-7 x-1");
+        assert_eq!(span.integers::<i32>().unwrap(), vec![-3, 14, 2, -7, -1]);
+        let err = Span::new("a 300").integers::<u8>().unwrap_err();
+        assert_eq!(err.column, 3);
+    }
+
+    #[test]
+    fn parses_grids() {
+        let cell = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        let grid = Span::new("#.\n.#").grid(cell).unwrap();
+        assert_eq!(grid, vec![vec![true, false], vec![false, true]]);
+        let err = Span::new("#.\n.?").grid(cell).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = Span::new("#.\n.").grid(cell).unwrap_err();
+        assert_eq!(
+            (err.line, err.reason.as_str()),
+            (2, "expected 2 columns, found 1")
+        );
+    }
+}
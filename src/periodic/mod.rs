@@ -0,0 +1,134 @@
+//! Numbers whose decimal digits are a single block repeated, like `1212` or `777`.
+//!
+//! A `k`-digit block `b` repeated `r` times is `b * (1 + 10^k + ... + 10^((r - 1)k))`,
+//! e.g. `12 * 10101 = 121212`. So the repeated numbers in a range are the
+//! multiples of that multiplier whose quotient has exactly `k` digits, which
+//! can be enumerated or summed directly without looking at a single digit.
+
+use std::ops::RangeInclusive;
+
+/// The number of decimal digits in `u64::MAX`.
+const MAX_DIGITS: u32 = 20;
+
+/// `1 + 10^k + 10^2k + ... + 10^((r - 1)k)`, which repeats a `k`-digit block `r` times.
+///
+/// ```
+/// # use advent_of_code::periodic::repeat_multiplier;
+/// assert_eq!(repeat_multiplier(3, 2), 1001);
+/// assert_eq!(repeat_multiplier(2, 3), 10101);
+/// ```
+pub const fn repeat_multiplier(k: u32, r: u32) -> u128 {
+    let mut m = 0;
+    let mut i = 0;
+    while i < r {
+        m = m * 10u128.pow(k) + 1;
+        i += 1;
+    }
+    m
+}
+
+/// The `k`-digit blocks `b` such that `b` repeated `r` times lies in `lo..=hi`.
+fn blocks(lo: u64, hi: u64, k: u32, r: u32) -> Option<(RangeInclusive<u128>, u128)> {
+    if k == 0 || r == 0 || k * r > MAX_DIGITS || lo > hi {
+        return None;
+    }
+    let m = repeat_multiplier(k, r);
+    let first = (lo as u128).div_ceil(m).max(10u128.pow(k - 1));
+    let last = (hi as u128 / m).min(10u128.pow(k) - 1);
+    (first <= last).then_some((first..=last, m))
+}
+
+/// All numbers in `lo..=hi` made of a `k`-digit block repeated `r` times, in ascending order.
+pub fn repeated(lo: u64, hi: u64, k: u32, r: u32) -> impl Iterator<Item = u64> {
+    blocks(lo, hi, k, r)
+        .into_iter()
+        .flat_map(|(blocks, m)| blocks.map(move |b| (b * m) as u64))
+}
+
+/// How many numbers in `lo..=hi` are made of a `k`-digit block repeated `r` times.
+pub fn count_repeated(lo: u64, hi: u64, k: u32, r: u32) -> u64 {
+    blocks(lo, hi, k, r).map_or(0, |(blocks, _)| (blocks.end() - blocks.start() + 1) as u64)
+}
+
+/// The sum of all numbers in `lo..=hi` made of a `k`-digit block repeated `r` times.
+pub fn sum_repeated(lo: u64, hi: u64, k: u32, r: u32) -> u128 {
+    blocks(lo, hi, k, r).map_or(0, |(blocks, m)| {
+        let (first, last) = (*blocks.start(), *blocks.end());
+        m * (first + last) * (last - first + 1) / 2
+    })
+}
+
+/// The sum of all numbers in `lo..=hi` made of some block repeated at least twice.
+///
+/// A number with `n` digits can be periodic with any period `d` that divides
+/// `n`, and a number with period `d` also has every period that `d` divides,
+/// e.g. `111111` is counted by `d = 1, 2, 3`. So for each `d` the sum of numbers
+/// whose *smallest* period is exactly `d` is worked out by subtracting the
+/// sums already found for the divisors of `d`, and only those are added up.
+pub fn sum_periodic(lo: u64, hi: u64) -> u128 {
+    (1..=MAX_DIGITS)
+        .filter_map(|n| {
+            let lo = lo.max(10u64.checked_pow(n - 1)?);
+            let hi = hi.min(10u64.checked_pow(n).map_or(u64::MAX, |x| x - 1));
+            (lo <= hi).then_some((n, lo, hi))
+        })
+        .map(|(n, lo, hi)| {
+            let periods: Vec<u32> = (1..n).filter(|d| n.is_multiple_of(*d)).collect();
+            let mut exact: Vec<u128> = Vec::with_capacity(periods.len());
+            for (i, &d) in periods.iter().enumerate() {
+                let shorter: u128 = periods[..i]
+                    .iter()
+                    .zip(&exact)
+                    .filter(|(e, _)| d.is_multiple_of(**e))
+                    .map(|(_, sum)| sum)
+                    .sum();
+                exact.push(sum_repeated(lo, hi, d, n / d) - shorter);
+            }
+            exact.iter().sum::<u128>()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_periodic(x: u64) -> bool {
+        let s = x.to_string();
+        (1..s.len()).any(|d| {
+            s.len().is_multiple_of(d) && s.as_bytes().chunks(d).all(|c| c == &s.as_bytes()[..d])
+        })
+    }
+
+    #[test]
+    fn enumerates_repeated_blocks() {
+        assert_eq!(
+            repeated(1, 100, 1, 2).collect::<Vec<_>>(),
+            (1..=9).map(|d| d * 11).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            repeated(1000, 1300, 2, 2).collect::<Vec<_>>(),
+            vec![1010, 1111, 1212]
+        );
+        assert_eq!(count_repeated(95, 115, 1, 2), 1);
+        assert_eq!(sum_repeated(95, 115, 1, 2), 99);
+        assert_eq!(count_repeated(0, u64::MAX, 9, 2), 900_000_000);
+        // doubled 10-digit blocks stop fitting in a u64 after 1844674407
+        assert_eq!(count_repeated(0, u64::MAX, 10, 2), 844_674_408);
+        assert_eq!(count_repeated(0, u64::MAX, 11, 2), 0);
+    }
+
+    #[test]
+    fn sums_periodic_numbers() {
+        for (lo, hi) in [
+            (1, 2000),
+            (95, 115),
+            (998, 1012),
+            (100_000, 130_000),
+            (1_188_511_880, 1_188_511_890),
+        ] {
+            let expected: u128 = (lo..=hi).filter(|x| is_periodic(*x)).map(u128::from).sum();
+            assert_eq!(sum_periodic(lo, hi), expected, "{lo}-{hi}");
+        }
+    }
+}
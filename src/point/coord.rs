@@ -0,0 +1,61 @@
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+/// A number that can be used as the coordinate of a point: any primitive
+/// integer or float.
+pub trait Coord:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + MulAssign
+    + Div<Output = Self>
+    + DivAssign
+    + Rem<Output = Self>
+    + RemAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn as_f64(self) -> f64;
+}
+
+/// A coordinate that can be negative, which is needed for unit vectors and
+/// rotations.
+pub trait Signed: Coord + Neg<Output = Self> {
+    const NEG_ONE: Self;
+}
+
+macro_rules! coord_impl {
+    ($zero:literal, $one:literal, $($t:ty),*) => {$(
+        impl Coord for $t {
+            const ZERO: Self = $zero;
+            const ONE: Self = $one;
+
+            #[inline]
+            fn as_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+coord_impl!(
+    0, 1, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+coord_impl!(0.0, 1.0, f32, f64);
+
+macro_rules! signed_impl {
+    ($neg_one:literal, $($t:ty),*) => {$(
+        impl Signed for $t {
+            const NEG_ONE: Self = $neg_one;
+        }
+    )*};
+}
+
+signed_impl!(-1, i8, i16, i32, i64, i128, isize);
+signed_impl!(-1.0, f32, f64);
@@ -0,0 +1,243 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// One of the four orthogonal directions, with north pointing up the screen
+/// (towards negative `y`).
+///
+/// Headings are ordered and indexed clockwise starting from north.
+///
+/// ```
+/// # use advent_of_code::point::Heading;
+/// let heading: Heading = "^".parse().unwrap();
+/// assert_eq!(heading.turn_right(), Heading::East);
+/// assert_eq!(Heading::from_char('L'), Some(Heading::West));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum Heading {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Heading {
+    /// Every heading, clockwise from north.
+    pub const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    #[inline]
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 3) % 4]
+    }
+
+    #[inline]
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 4]
+    }
+
+    #[inline]
+    pub const fn reverse(self) -> Self {
+        Self::ALL[(self.index() + 2) % 4]
+    }
+
+    /// The position of the heading in [`Heading::ALL`].
+    #[inline]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The heading at `index` in [`Heading::ALL`], or [`None`] if it is out of range.
+    #[inline]
+    pub const fn from_index(index: usize) -> Option<Self> {
+        if index < 4 {
+            Some(Self::ALL[index])
+        } else {
+            None
+        }
+    }
+
+    /// Reads an arrow (`^>v<`), a compass point (`NESW`) or a screen direction
+    /// (`UDLR`), returns [`None`] for anything else.
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'N' | 'U' => Some(Self::North),
+            '>' | 'E' | 'R' => Some(Self::East),
+            'v' | 'S' | 'D' => Some(Self::South),
+            '<' | 'W' | 'L' => Some(Self::West),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Heading {
+    type Err = HeadingFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::from_char(c).ok_or(HeadingFromStrError),
+            _ => Err(HeadingFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Heading`].
+#[derive(Debug)]
+pub struct HeadingFromStrError;
+
+impl Error for HeadingFromStrError {}
+
+impl Display for HeadingFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of ^>v<, NESW or UDLR")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the eight directions to a neighboring cell, including diagonals.
+///
+/// Headings are ordered and indexed clockwise starting from north, so turning
+/// right goes through 45 degrees.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum Heading8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Heading8 {
+    /// Every heading, clockwise from north.
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /// Turns 45 degrees anticlockwise.
+    #[inline]
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    /// Turns 45 degrees clockwise.
+    #[inline]
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    #[inline]
+    pub const fn reverse(self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    #[inline]
+    pub const fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// The position of the heading in [`Heading8::ALL`].
+    #[inline]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The heading at `index` in [`Heading8::ALL`], or [`None`] if it is out of range.
+    #[inline]
+    pub const fn from_index(index: usize) -> Option<Self> {
+        if index < 8 {
+            Some(Self::ALL[index])
+        } else {
+            None
+        }
+    }
+}
+
+impl From<Heading> for Heading8 {
+    fn from(heading: Heading) -> Self {
+        Self::ALL[2 * heading.index()]
+    }
+}
+
+impl TryFrom<Heading8> for Heading {
+    type Error = Heading8;
+
+    /// Fails with the original heading if it is diagonal.
+    fn try_from(heading: Heading8) -> Result<Self, Self::Error> {
+        if heading.is_diagonal() {
+            Err(heading)
+        } else {
+            Ok(Self::ALL[heading.index() / 2])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns_are_consistent() {
+        for heading in Heading::ALL {
+            assert_eq!(heading.turn_left().turn_right(), heading);
+            assert_eq!(heading.turn_right().turn_right(), heading.reverse());
+            assert_eq!(heading.reverse().reverse(), heading);
+            assert_eq!(Heading::from_index(heading.index()), Some(heading));
+        }
+        for heading in Heading8::ALL {
+            assert_eq!(heading.turn_left().turn_right(), heading);
+            assert_eq!(heading.reverse().reverse(), heading);
+            assert_ne!(heading.turn_right().is_diagonal(), heading.is_diagonal());
+            assert_eq!(Heading8::from_index(heading.index()), Some(heading));
+        }
+        assert_eq!(Heading::North.turn_left(), Heading::West);
+        assert_eq!(Heading::West.turn_right(), Heading::North);
+        assert_eq!(Heading8::North.turn_left(), Heading8::NorthWest);
+        assert_eq!(Heading8::SouthEast.reverse(), Heading8::NorthWest);
+        assert_eq!(Heading::from_index(4), None);
+        assert_eq!(Heading8::from_index(8), None);
+    }
+
+    #[test]
+    fn parses_arrows_compass_points_and_screen_directions() {
+        let parse = |s: &str| {
+            s.chars()
+                .map(Heading::from_char)
+                .collect::<Option<Vec<_>>>()
+        };
+        for s in ["^>v<", "NESW", "URDL"] {
+            assert_eq!(parse(s), Some(Heading::ALL.to_vec()));
+        }
+        assert_eq!(Heading::from_char('x'), None);
+        assert_eq!("v".parse::<Heading>().unwrap(), Heading::South);
+        assert!("".parse::<Heading>().is_err());
+        assert!("NE".parse::<Heading>().is_err());
+    }
+
+    #[test]
+    fn converts_between_four_and_eight_directions() {
+        for heading in Heading::ALL {
+            let heading8 = Heading8::from(heading);
+            assert!(!heading8.is_diagonal());
+            assert_eq!(Heading::try_from(heading8), Ok(heading));
+            assert_eq!(
+                Heading8::from(heading.turn_right()),
+                heading8.turn_right().turn_right()
+            );
+        }
+        assert_eq!(
+            Heading::try_from(Heading8::SouthWest),
+            Err(Heading8::SouthWest)
+        );
+    }
+}
@@ -0,0 +1,378 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use super::{Point2, Point3, Signed, abs_diff};
+
+/// A cell of a hexagonal grid in axial coordinates.
+///
+/// The third cube coordinate is implied, `q + r + s == 0`. The axes are the
+/// same whichever way up the hexagons are, only the names of the six
+/// directions and the mapping to offset coordinates differ, see
+/// [`FlatHeading`], [`PointyHeading`] and [`HexOrientation`].
+///
+/// ```
+/// # use advent_of_code::point::{Hex, PointyHeading};
+/// let moves = PointyHeading::parse_moves("nwwswee").unwrap();
+/// let end = moves.into_iter().fold(Hex::zero(), Hex::step);
+/// assert_eq!(end, Hex::<i32>::zero());
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub struct Hex<T> {
+    pub q: T,
+    pub r: T,
+}
+
+/// Which way up the hexagons are drawn.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum HexOrientation {
+    /// Columns of hexagons with a flat edge at the top, every odd column shoved
+    /// down by half a cell in offset coordinates ("odd-q").
+    FlatTop,
+    /// Rows of hexagons with a corner at the top, every odd row shoved right by
+    /// half a cell in offset coordinates ("odd-r").
+    PointyTop,
+}
+
+impl<T> Hex<T> {
+    #[inline]
+    pub const fn new(q: T, r: T) -> Self {
+        Self { q, r }
+    }
+}
+
+impl<T: Signed> Hex<T> {
+    #[inline]
+    pub const fn zero() -> Self {
+        Self::new(T::ZERO, T::ZERO)
+    }
+
+    /// The implied third cube coordinate.
+    #[inline]
+    pub fn s(self) -> T {
+        -self.q - self.r
+    }
+
+    /// The cube coordinates `(q, r, s)`.
+    #[inline]
+    pub fn cube(self) -> Point3<T> {
+        Point3::new(self.q, self.r, self.s())
+    }
+
+    /// Reads cube coordinates, returns [`None`] if they don't sum to zero.
+    #[inline]
+    pub fn from_cube(p: Point3<T>) -> Option<Self> {
+        (p.x + p.y + p.z == T::ZERO).then_some(Self::new(p.x, p.y))
+    }
+
+    #[inline]
+    pub fn step(self, heading: impl Into<Self>) -> Self {
+        self + heading.into()
+    }
+
+    /// The number of steps between the cells.
+    #[inline]
+    pub fn distance(self, other: Self) -> T {
+        let d =
+            abs_diff(self.q, other.q) + abs_diff(self.r, other.r) + abs_diff(self.s(), other.s());
+        d / (T::ONE + T::ONE)
+    }
+
+    /// The six adjacent cells, which are the same in both orientations.
+    #[inline]
+    pub fn neighbors(self) -> [Self; 6] {
+        PointyHeading::ALL.map(|heading| self.step(heading))
+    }
+
+    /// Rotates 60 degrees clockwise about the origin.
+    #[inline]
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.r, -self.s())
+    }
+
+    /// Rotates 60 degrees anticlockwise about the origin.
+    #[inline]
+    pub fn rotate_left(self) -> Self {
+        Self::new(-self.s(), -self.q)
+    }
+
+    /// The column and row of the cell in a rectangular layout.
+    pub fn to_offset(self, orientation: HexOrientation) -> Point2<T> {
+        match orientation {
+            HexOrientation::FlatTop => Point2::new(self.q, self.r + half_down(self.q)),
+            HexOrientation::PointyTop => Point2::new(self.q + half_down(self.r), self.r),
+        }
+    }
+
+    /// The cell at a column and row of a rectangular layout.
+    pub fn from_offset(p: Point2<T>, orientation: HexOrientation) -> Self {
+        match orientation {
+            HexOrientation::FlatTop => Self::new(p.x, p.y - half_down(p.x)),
+            HexOrientation::PointyTop => Self::new(p.x - half_down(p.y), p.y),
+        }
+    }
+}
+
+/// `n / 2` rounded towards negative infinity.
+#[inline]
+fn half_down<T: Signed>(n: T) -> T {
+    let two = T::ONE + T::ONE;
+    let odd = if n % two == T::ZERO { T::ZERO } else { T::ONE };
+    (n - odd) / two
+}
+
+impl<T: Signed> Add for Hex<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl<T: Signed> AddAssign for Hex<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.q += other.q;
+        self.r += other.r;
+    }
+}
+
+impl<T: Signed> Sub for Hex<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl<T: Signed> SubAssign for Hex<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.q -= other.q;
+        self.r -= other.r;
+    }
+}
+
+impl<T: Signed> Neg for Hex<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.q, -self.r)
+    }
+}
+
+impl<T: Signed> Mul<T> for Hex<T> {
+    type Output = Self;
+
+    fn mul(self, other: T) -> Self {
+        Self::new(self.q * other, self.r * other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+macro_rules! hex_heading {
+    (
+        $(#[$meta:meta])*
+        $name:ident { $($variant:ident = $token:literal ($q:literal, $r:literal)),* $(,)? }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+        pub enum $name {
+            $($variant),*
+        }
+
+        impl $name {
+            /// Every heading, clockwise.
+            pub const ALL: [Self; 6] = [$(Self::$variant),*];
+
+            /// Turns 60 degrees anticlockwise.
+            #[inline]
+            pub const fn turn_left(self) -> Self {
+                Self::ALL[(self.index() + 5) % 6]
+            }
+
+            /// Turns 60 degrees clockwise.
+            #[inline]
+            pub const fn turn_right(self) -> Self {
+                Self::ALL[(self.index() + 1) % 6]
+            }
+
+            #[inline]
+            pub const fn reverse(self) -> Self {
+                Self::ALL[(self.index() + 3) % 6]
+            }
+
+            #[inline]
+            pub const fn index(self) -> usize {
+                self as usize
+            }
+
+            /// Splits a string of moves, with or without separators between them,
+            /// like `"nwwswee"` or `"n,ne,s"`.
+            pub fn parse_moves(s: &str) -> Result<Vec<Self>, HexHeadingFromStrError> {
+                let mut moves = vec![];
+                let mut rest = s.trim_start_matches([',', ' ']);
+                while !rest.is_empty() {
+                    let (heading, len) = [2, 1]
+                        .into_iter()
+                        .filter_map(|len| Some((rest.get(..len)?.parse().ok()?, len)))
+                        .next()
+                        .ok_or(HexHeadingFromStrError)?;
+                    moves.push(heading);
+                    rest = rest[len..].trim_start_matches([',', ' ']);
+                }
+                Ok(moves)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = HexHeadingFromStrError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($token => Ok(Self::$variant),)*
+                    _ => Err(HexHeadingFromStrError),
+                }
+            }
+        }
+
+        impl<T: Signed> From<$name> for Hex<T> {
+            fn from(heading: $name) -> Self {
+                let (zero, one) = (T::ZERO, T::ONE);
+                let unit = |c: i8| match c {
+                    -1 => -one,
+                    0 => zero,
+                    _ => one,
+                };
+                match heading {
+                    $($name::$variant => Self::new(unit($q), unit($r)),)*
+                }
+            }
+        }
+    };
+}
+
+hex_heading! {
+    /// A direction on a grid of flat-topped hexagons, which has north and south
+    /// neighbors but none to the east and west.
+    FlatHeading {
+        North = "n" (0, -1),
+        NorthEast = "ne" (1, -1),
+        SouthEast = "se" (1, 0),
+        South = "s" (0, 1),
+        SouthWest = "sw" (-1, 1),
+        NorthWest = "nw" (-1, 0),
+    }
+}
+
+hex_heading! {
+    /// A direction on a grid of pointy-topped hexagons, which has east and west
+    /// neighbors but none to the north and south.
+    PointyHeading {
+        East = "e" (1, 0),
+        SouthEast = "se" (0, 1),
+        SouthWest = "sw" (-1, 1),
+        West = "w" (-1, 0),
+        NorthWest = "nw" (0, -1),
+        NorthEast = "ne" (1, -1),
+    }
+}
+
+/// An error which can be returned when parsing a [`FlatHeading`] or a [`PointyHeading`].
+#[derive(Debug)]
+pub struct HexHeadingFromStrError;
+
+impl Error for HexHeadingFromStrError {}
+
+impl Display for HexHeadingFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting moves like n, ne, e, se, s, sw, w or nw")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential::forall;
+
+    fn walk<H: Into<Hex<i32>>>(moves: Vec<H>) -> Hex<i32> {
+        moves.into_iter().fold(Hex::zero(), Hex::step)
+    }
+
+    #[test]
+    fn walks_flat_top_moves() {
+        let distance = |s| walk(FlatHeading::parse_moves(s).unwrap()).distance(Hex::zero());
+        assert_eq!(distance("ne,ne,ne"), 3);
+        assert_eq!(distance("ne,ne,sw,sw"), 0);
+        assert_eq!(distance("ne,ne,s,s"), 2);
+        assert_eq!(distance("se,sw,se,sw,sw"), 3);
+        assert!(FlatHeading::parse_moves("n,e").is_err());
+    }
+
+    #[test]
+    fn walks_pointy_top_moves() {
+        let end = |s| walk(PointyHeading::parse_moves(s).unwrap());
+        assert_eq!(end("esew"), Hex::zero().step(PointyHeading::SouthEast));
+        assert_eq!(end("nwwswee"), Hex::zero());
+        assert_eq!(end("e, e, nw"), Hex::new(2, -1));
+        assert!(PointyHeading::parse_moves("").unwrap().is_empty());
+        assert!(PointyHeading::parse_moves("esn").is_err());
+    }
+
+    #[test]
+    fn headings_turn_and_reverse() {
+        for heading in PointyHeading::ALL {
+            let unit = Hex::<i32>::from(heading);
+            assert_eq!(Hex::from(heading.reverse()), -unit);
+            assert_eq!(Hex::from(heading.turn_right()), unit.rotate_right());
+            assert_eq!(heading.turn_left().turn_right(), heading);
+        }
+        for heading in FlatHeading::ALL {
+            let unit = Hex::<i32>::from(heading);
+            assert_eq!(Hex::from(heading.reverse()), -unit);
+            assert_eq!(Hex::from(heading.turn_left()), unit.rotate_left());
+            assert_eq!(unit.distance(Hex::zero()), 1);
+        }
+    }
+
+    #[test]
+    fn distances_and_neighbors() {
+        let hex = |g: &mut crate::differential::Gen| (g.signed(-50..=50), g.signed(-50..=50));
+        forall(
+            |g| (hex(g), hex(g), hex(g)),
+            |&((aq, ar), (bq, br), (cq, cr))| {
+                let (a, b, c) = (Hex::new(aq, ar), Hex::new(bq, br), Hex::new(cq, cr));
+                a.distance(b) == b.distance(a)
+                    && a.distance(c) <= a.distance(b) + b.distance(c)
+                    && a.distance(b) == (a - b).distance(Hex::zero())
+                    && a.neighbors().iter().all(|&n| n.distance(a) == 1)
+                    && a.rotate_right().rotate_left() == a
+                    && Hex::from_cube(a.cube()) == Some(a)
+            },
+        );
+        assert_eq!(Hex::from_cube(Point3::new(1, 1, 1)), None);
+    }
+
+    #[test]
+    fn converts_to_and_from_offsets() {
+        for orientation in [HexOrientation::FlatTop, HexOrientation::PointyTop] {
+            forall(
+                |g| (g.signed(-50..=50), g.signed(-50..=50)),
+                |&(x, y)| {
+                    let p = Point2::new(x, y);
+                    Hex::from_offset(p, orientation).to_offset(orientation) == p
+                },
+            );
+        }
+        // odd rows are shoved right, so south-east of an even row keeps the column
+        let pointy = |q, r| Hex::new(q, r).to_offset(HexOrientation::PointyTop);
+        assert_eq!(pointy(0, 1), Point2::new(0, 1));
+        assert_eq!(pointy(-1, 2), Point2::new(0, 2));
+        assert_eq!(pointy(0, -1), Point2::new(-1, -1));
+        // odd columns are shoved down, so north-east of an even column keeps the row
+        let flat = |q, r| Hex::new(q, r).to_offset(HexOrientation::FlatTop);
+        assert_eq!(flat(1, -1), Point2::new(1, -1));
+        assert_eq!(flat(1, 0), Point2::new(1, 0));
+        assert_eq!(flat(-1, 0), Point2::new(-1, -1));
+    }
+}
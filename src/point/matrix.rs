@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Mul;
+
+use super::{Point3, Signed};
+
+/// A 3x3 matrix stored as rows, mostly for rotating [`Point3`]s.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub struct Matrix3<T>(pub [[T; 3]; 3]);
+
+impl<T: Signed> Matrix3<T> {
+    #[inline]
+    pub const fn new(rows: [[T; 3]; 3]) -> Self {
+        Self(rows)
+    }
+
+    pub fn identity() -> Self {
+        let (o, l) = (T::ZERO, T::ONE);
+        Self([[l, o, o], [o, l, o], [o, o, l]])
+    }
+
+    /// Builds the matrix from the images of the x, y and z unit vectors.
+    pub fn from_columns(x: Point3<T>, y: Point3<T>, z: Point3<T>) -> Self {
+        Self([[x.x, y.x, z.x], [x.y, y.y, z.y], [x.z, y.z, z.z]])
+    }
+
+    #[inline]
+    pub fn row(&self, i: usize) -> Point3<T> {
+        let [x, y, z] = self.0[i];
+        Point3::new(x, y, z)
+    }
+
+    #[inline]
+    pub fn column(&self, j: usize) -> Point3<T> {
+        Point3::new(self.0[0][j], self.0[1][j], self.0[2][j])
+    }
+
+    /// The transpose, which is also the inverse of a rotation.
+    pub fn transpose(&self) -> Self {
+        Self::from_columns(self.row(0), self.row(1), self.row(2))
+    }
+
+    pub fn determinant(&self) -> T {
+        self.row(0).dot(self.row(1).cross(self.row(2)))
+    }
+
+    /// The 24 rotations that map the axes onto the axes, starting with the
+    /// identity. These are the ways to orient a cube, or a scanner that doesn't
+    /// know which way it is facing.
+    pub fn rotations() -> [Self; 24] {
+        let (o, l) = (T::ZERO, T::ONE);
+        let axes = [
+            Point3::new(l, o, o),
+            Point3::new(o, l, o),
+            Point3::new(o, o, l),
+        ];
+        let mut rotations = Vec::with_capacity(24);
+        for (i, j) in [(0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1)] {
+            for (si, sj) in [(l, l), (l, -l), (-l, l), (-l, -l)] {
+                let x = axes[i] * si;
+                let y = axes[j] * sj;
+                // the image of z is forced by keeping the handedness
+                rotations.push(Self::from_columns(x, y, x.cross(y)));
+            }
+        }
+        rotations
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("6 axis pairs with 4 sign choices each"))
+    }
+}
+
+impl<T: Signed> Mul for Matrix3<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::from_columns(
+            self * other.column(0),
+            self * other.column(1),
+            self * other.column(2),
+        )
+    }
+}
+
+impl<T: Signed> Mul<Point3<T>> for Matrix3<T> {
+    type Output = Point3<T>;
+
+    fn mul(self, p: Point3<T>) -> Point3<T> {
+        Point3::new(self.row(0).dot(p), self.row(1).dot(p), self.row(2).dot(p))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rotation followed by a translation.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Transform<T> {
+    pub rotation: Matrix3<T>,
+    pub translation: Point3<T>,
+}
+
+impl<T: Signed> Transform<T> {
+    #[inline]
+    pub fn apply(&self, p: Point3<T>) -> Point3<T> {
+        self.rotation * p + self.translation
+    }
+
+    /// The transform that undoes this one.
+    pub fn inverse(&self) -> Self {
+        let rotation = self.rotation.transpose();
+        Self {
+            rotation,
+            translation: -(rotation * self.translation),
+        }
+    }
+
+    /// Applies `self` first, then `other`.
+    pub fn then(&self, other: &Self) -> Self {
+        Self {
+            rotation: other.rotation * self.rotation,
+            translation: other.apply(self.translation),
+        }
+    }
+}
+
+/// Finds how to rotate and translate `points` so that at least `min_overlap`
+/// of them land on `reference`, by trying every axis-aligned rotation and
+/// counting how often each translation occurs. Takes `O(24 * n * m)` time.
+///
+/// ```
+/// # use advent_of_code::point::{Point3, align};
+/// let reference = [Point3::new(0, 0, 0), Point3::new(1, 2, 3), Point3::new(5, 0, -1)];
+/// let moved = reference.map(|p| Point3::new(-p.y, p.x, p.z) + Point3::new(10, 0, 0));
+/// let transform = align(&reference, &moved, 3).unwrap();
+/// assert_eq!(moved.map(|p| transform.apply(p)), reference);
+/// ```
+pub fn align<T>(
+    reference: &[Point3<T>],
+    points: &[Point3<T>],
+    min_overlap: usize,
+) -> Option<Transform<T>>
+where
+    T: Signed + Eq + Hash,
+{
+    let mut votes = HashMap::new();
+    for rotation in Matrix3::rotations() {
+        votes.clear();
+        for &p in points {
+            let p = rotation * p;
+            for &q in reference {
+                let count = votes.entry(q - p).or_insert(0);
+                *count += 1;
+                if *count >= min_overlap {
+                    return Some(Transform {
+                        rotation,
+                        translation: q - p,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::differential::{Gen, forall};
+
+    type P3 = (i64, i64, i64);
+
+    fn p3(g: &mut Gen) -> P3 {
+        (
+            g.signed(-100..=100),
+            g.signed(-100..=100),
+            g.signed(-100..=100),
+        )
+    }
+
+    fn point((x, y, z): P3) -> Point3<i64> {
+        Point3::new(x, y, z)
+    }
+
+    #[test]
+    fn rotations_form_a_group() {
+        let rotations = Matrix3::<i32>::rotations();
+        let set: HashSet<_> = rotations.into_iter().collect();
+        assert_eq!(set.len(), 24);
+        assert_eq!(rotations[0], Matrix3::identity());
+        for a in rotations {
+            assert_eq!(a.determinant(), 1);
+            assert_eq!(a * a.transpose(), Matrix3::identity());
+            assert!(rotations.iter().all(|&b| set.contains(&(a * b))));
+        }
+    }
+
+    #[test]
+    fn cross_and_dot_products() {
+        forall(
+            |g| (p3(g), p3(g), g.range(0..=23) as usize),
+            |&(a, b, r)| {
+                let (a, b) = (point(a), point(b));
+                let rotation = Matrix3::rotations()[r];
+                let c = a.cross(b);
+                c.dot(a) == 0
+                    && c.dot(b) == 0
+                    && b.cross(a) == -c
+                    && a.dot(b) == b.dot(a)
+                    && a.dot(a) == a.dist_squared(Point3::zero())
+                    && rotation * c == (rotation * a).cross(rotation * b)
+                    && (rotation * a).dot(rotation * b) == a.dot(b)
+            },
+        );
+        let (x, y, z) = (
+            Point3::new(1, 0, 0),
+            Point3::new(0, 1, 0),
+            Point3::new(0, 0, 1),
+        );
+        assert_eq!(x.cross(y), z);
+        assert_eq!(y.cross(z), x);
+        assert_eq!(Point3::new(1, 2, 3).dot(Point3::new(4, -5, 6)), 12);
+    }
+
+    #[test]
+    fn transforms_compose_and_invert() {
+        forall(
+            |g| {
+                (
+                    (p3(g), p3(g), p3(g)),
+                    (g.range(0..=23) as usize, g.range(0..=23) as usize),
+                )
+            },
+            |&((p, s, t), (i, j))| {
+                let rotations = Matrix3::rotations();
+                let a = Transform {
+                    rotation: rotations[i],
+                    translation: point(s),
+                };
+                let b = Transform {
+                    rotation: rotations[j],
+                    translation: point(t),
+                };
+                let p = point(p);
+                a.inverse().apply(a.apply(p)) == p && a.then(&b).apply(p) == b.apply(a.apply(p))
+            },
+        );
+    }
+
+    #[test]
+    fn aligns_overlapping_clouds() {
+        forall(
+            |g| (g.vec(p3), g.vec(p3), p3(g), g.range(0..=23) as usize),
+            |(shared, extra, shift, r)| {
+                let shared: Vec<_> = shared
+                    .iter()
+                    .copied()
+                    .map(point)
+                    .collect::<HashSet<_>>()
+                    .into_iter()
+                    .collect();
+                if shared.len() < 4 {
+                    return true;
+                }
+                // what a scanner at `shift`, facing another way, would see
+                let seen = Transform {
+                    rotation: Matrix3::rotations()[*r],
+                    translation: point(*shift),
+                };
+                let mut points: Vec<_> = shared.iter().map(|&p| seen.apply(p)).collect();
+                points.extend(extra.iter().map(|&p| point(p) * 1000 + point((1, 1, 1))));
+
+                let Some(found) = align(&shared, &points, shared.len()) else {
+                    return false;
+                };
+                let reference: HashSet<_> = shared.iter().copied().collect();
+                points
+                    .iter()
+                    .filter(|&&p| reference.contains(&found.apply(p)))
+                    .count()
+                    >= shared.len()
+            },
+        );
+        let cloud = [Point3::new(0, 0, 0), Point3::new(1, 0, 0)];
+        assert_eq!(align(&cloud, &[Point3::new(7, 7, 7)], 2), None);
+    }
+}
@@ -1,5 +1,20 @@
 use std::fmt::Display;
-use std::ops::{Add, AddAssign, Mul, MulAssign};
+use std::num::TryFromIntError;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+pub use coord::*;
+pub use heading::*;
+pub use hex::*;
+pub use matrix::*;
+pub use point_n::*;
+
+mod coord;
+mod heading;
+mod hex;
+mod matrix;
+mod point_n;
 
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
 pub struct Point2<T> {
@@ -12,6 +27,12 @@ impl<T> Point2<T> {
     pub const fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
+
+    /// Applies `f` to every coordinate, e.g. to convert them with `as`.
+    #[inline]
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Point2<U> {
+        Point2::new(f(self.x), f(self.y))
+    }
 }
 
 impl<T: Display> Display for Point2<T> {
@@ -20,155 +41,278 @@ impl<T: Display> Display for Point2<T> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
-pub enum Heading {
-    North,
-    East,
-    South,
-    West,
+impl<T: Coord> Point2<T> {
+    #[inline]
+    pub const fn zero() -> Self {
+        Self::new(T::ZERO, T::ZERO)
+    }
+
+    #[inline]
+    pub fn scale(self, f: T) -> Self {
+        Self::new(self.x * f, self.y * f)
+    }
+
+    /// The component-wise minimum. Note that `Ord::min` compares lexicographically instead.
+    #[inline]
+    pub fn component_min(self, other: Self) -> Self {
+        Self::new(min(self.x, other.x), min(self.y, other.y))
+    }
+
+    /// The component-wise maximum. Note that `Ord::max` compares lexicographically instead.
+    #[inline]
+    pub fn component_max(self, other: Self) -> Self {
+        Self::new(max(self.x, other.x), max(self.y, other.y))
+    }
+
+    #[inline]
+    pub fn dist_squared(self, other: Self) -> T {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        dx * dx + dy * dy
+    }
+
+    #[inline]
+    pub fn dist(self, other: Self) -> f64 {
+        self.dist_squared(other).as_f64().sqrt()
+    }
+
+    /// The number of orthogonal steps between the points.
+    #[inline]
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The number of king moves, which may be diagonal, between the points.
+    #[inline]
+    pub fn chebyshev(self, other: Self) -> T {
+        max(abs_diff(self.x, other.x), abs_diff(self.y, other.y))
+    }
 }
 
-macro_rules! point2_impl {
-    ($t:ty) => {
-        impl Point2<$t> {
-            #[inline]
-            pub const fn zero() -> Self {
-                Self::new(0, 0)
-            }
+impl<T: Signed> Point2<T> {
+    #[inline]
+    pub fn step(self, heading: Heading) -> Self {
+        self + Self::unit(heading)
+    }
 
-            #[inline]
-            pub const fn add(self, other: Self) -> Self {
-                Self::new(self.x + other.x, self.y + other.y)
-            }
+    #[inline]
+    pub const fn unit(heading: Heading) -> Self {
+        match heading {
+            Heading::North => Self::new(T::ZERO, T::NEG_ONE),
+            Heading::East => Self::new(T::ONE, T::ZERO),
+            Heading::South => Self::new(T::ZERO, T::ONE),
+            Heading::West => Self::new(T::NEG_ONE, T::ZERO),
+        }
+    }
 
-            #[inline]
-            pub const fn scale(self, f: $t) -> Self {
-                Self::new(self.x * f, self.y * f)
-            }
+    #[inline]
+    pub fn step8(self, heading: Heading8) -> Self {
+        self + Self::unit8(heading)
+    }
 
-            #[inline]
-            pub const fn dist_squared(self, other: Self) -> $t {
-                let dx = self.x - other.x;
-                let dy = self.y - other.y;
-                dx * dx + dy * dy
-            }
+    #[inline]
+    pub const fn unit8(heading: Heading8) -> Self {
+        match heading {
+            Heading8::North => Self::new(T::ZERO, T::NEG_ONE),
+            Heading8::NorthEast => Self::new(T::ONE, T::NEG_ONE),
+            Heading8::East => Self::new(T::ONE, T::ZERO),
+            Heading8::SouthEast => Self::new(T::ONE, T::ONE),
+            Heading8::South => Self::new(T::ZERO, T::ONE),
+            Heading8::SouthWest => Self::new(T::NEG_ONE, T::ONE),
+            Heading8::West => Self::new(T::NEG_ONE, T::ZERO),
+            Heading8::NorthWest => Self::new(T::NEG_ONE, T::NEG_ONE),
+        }
+    }
 
-            #[inline]
-            pub fn dist(self, other: Self) -> f64 {
-                (self.dist_squared(other) as f64).sqrt()
-            }
+    /// Rotates about the origin so that a vector pointing north ends up pointing
+    /// towards `heading`.
+    #[inline]
+    pub fn rotate(self, heading: Heading) -> Self {
+        match heading {
+            Heading::North => self,
+            Heading::East => Self::new(-self.y, self.x),
+            Heading::South => Self::new(-self.x, -self.y),
+            Heading::West => Self::new(self.y, -self.x),
+        }
+    }
 
-            #[inline]
-            pub const fn step(self, heading: Heading) -> Self {
-                self.add(Self::unit(heading))
-            }
+    #[inline]
+    pub fn left(self) -> Self {
+        self.step(Heading::West)
+    }
 
-            #[inline]
-            pub const fn unit(heading: Heading) -> Self {
-                match heading {
-                    Heading::North => Self::new(0, -1),
-                    Heading::East => Self::new(1, 0),
-                    Heading::South => Self::new(0, 1),
-                    Heading::West => Self::new(-1, 0),
-                }
-            }
+    #[inline]
+    pub fn right(self) -> Self {
+        self.step(Heading::East)
+    }
 
-            #[inline]
-            pub const fn left(self) -> Self {
-                self.step(Heading::West)
-            }
+    #[inline]
+    pub fn up(self) -> Self {
+        self.step(Heading::North)
+    }
 
-            #[inline]
-            pub const fn right(self) -> Self {
-                self.step(Heading::East)
-            }
+    #[inline]
+    pub fn down(self) -> Self {
+        self.step(Heading::South)
+    }
 
-            #[inline]
-            pub const fn up(self) -> Self {
-                self.step(Heading::North)
-            }
+    #[inline]
+    pub fn neighbors4(self) -> [Self; 4] {
+        Self::dirs4().map(|d| self + d)
+    }
 
-            #[inline]
-            pub const fn down(self) -> Self {
-                self.step(Heading::South)
-            }
+    #[inline]
+    pub fn neighbors8(self) -> [Self; 8] {
+        Self::dirs8().map(|d| self + d)
+    }
 
-            #[inline]
-            pub fn neighbors4(self) -> [Self; 4] {
-                Self::dirs4().map(|d| self + d)
-            }
+    pub const fn dirs4() -> [Self; 4] {
+        [
+            Self::unit(Heading::North),
+            Self::unit(Heading::East),
+            Self::unit(Heading::South),
+            Self::unit(Heading::West),
+        ]
+    }
 
-            #[inline]
-            pub fn neighbors8(self) -> [Self; 8] {
-                Self::dirs8().map(|d| self + d)
-            }
+    pub const fn dirs8() -> [Self; 8] {
+        [
+            Self::unit8(Heading8::North),
+            Self::unit8(Heading8::NorthEast),
+            Self::unit8(Heading8::East),
+            Self::unit8(Heading8::SouthEast),
+            Self::unit8(Heading8::South),
+            Self::unit8(Heading8::SouthWest),
+            Self::unit8(Heading8::West),
+            Self::unit8(Heading8::NorthWest),
+        ]
+    }
+}
 
-            pub const fn dirs4() -> [Self; 4] {
-                [
-                    Self::unit(Heading::North),
-                    Self::unit(Heading::East),
-                    Self::unit(Heading::South),
-                    Self::unit(Heading::West),
-                ]
-            }
+impl Point2<usize> {
+    /// Moves a grid index by an offset, returns [`None`] if it would leave the
+    /// first quadrant.
+    #[inline]
+    pub fn checked_add_signed(self, offset: Point2<isize>) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add_signed(offset.x)?,
+            self.y.checked_add_signed(offset.y)?,
+        ))
+    }
+}
 
-            pub const fn dirs8() -> [Self; 8] {
-                [
-                    Self::unit(Heading::North),
-                    Self::unit(Heading::North).add(Self::unit(Heading::East)),
-                    Self::unit(Heading::East),
-                    Self::unit(Heading::East).add(Self::unit(Heading::South)),
-                    Self::unit(Heading::South),
-                    Self::unit(Heading::South).add(Self::unit(Heading::West)),
-                    Self::unit(Heading::West),
-                    Self::unit(Heading::West).add(Self::unit(Heading::North)),
-                ]
-            }
-        }
+impl<T: Signed> From<Heading> for Point2<T> {
+    fn from(heading: Heading) -> Self {
+        Self::unit(heading)
+    }
+}
 
-        impl From<Heading> for Point2<$t> {
-            fn from(heading: Heading) -> Self {
-                Self::unit(heading)
-            }
-        }
+impl<T: Signed> From<Heading8> for Point2<T> {
+    fn from(heading: Heading8) -> Self {
+        Self::unit8(heading)
+    }
+}
 
-        impl Add for Point2<$t> {
-            type Output = Self;
+impl TryFrom<Point2<isize>> for Point2<usize> {
+    type Error = TryFromIntError;
 
-            fn add(self, other: Self) -> Self {
-                self.add(other)
-            }
-        }
+    fn try_from(p: Point2<isize>) -> Result<Self, Self::Error> {
+        Ok(Self::new(p.x.try_into()?, p.y.try_into()?))
+    }
+}
 
-        impl AddAssign<Point2<$t>> for Point2<$t> {
-            fn add_assign(&mut self, other: Self) {
-                self.x += other.x;
-                self.y += other.y;
-            }
-        }
+impl TryFrom<Point2<usize>> for Point2<isize> {
+    type Error = TryFromIntError;
 
-        impl Mul<$t> for Point2<$t> {
-            type Output = Self;
+    fn try_from(p: Point2<usize>) -> Result<Self, Self::Error> {
+        Ok(Self::new(p.x.try_into()?, p.y.try_into()?))
+    }
+}
 
-            fn mul(self, other: $t) -> Self {
-                self.scale(other)
-            }
-        }
+impl<T: Coord> Add for Point2<T> {
+    type Output = Self;
 
-        impl MulAssign<$t> for Point2<$t> {
-            fn mul_assign(&mut self, other: $t) {
-                self.x *= other;
-                self.y *= other;
-            }
-        }
-    };
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Coord> AddAssign for Point2<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: Coord> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Coord> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Signed> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Coord> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, other: T) -> Self {
+        self.scale(other)
+    }
+}
+
+impl<T: Coord> MulAssign<T> for Point2<T> {
+    fn mul_assign(&mut self, other: T) {
+        self.x *= other;
+        self.y *= other;
+    }
+}
+
+impl<T: Coord> Div<T> for Point2<T> {
+    type Output = Self;
+
+    fn div(self, other: T) -> Self {
+        Self::new(self.x / other, self.y / other)
+    }
 }
 
-point2_impl!(i8);
-point2_impl!(i16);
-point2_impl!(i32);
-point2_impl!(i64);
-point2_impl!(isize);
+impl<T: Coord> DivAssign<T> for Point2<T> {
+    fn div_assign(&mut self, other: T) {
+        self.x /= other;
+        self.y /= other;
+    }
+}
+
+impl<T: Coord> Rem<T> for Point2<T> {
+    type Output = Self;
+
+    fn rem(self, other: T) -> Self {
+        Self::new(self.x % other, self.y % other)
+    }
+}
+
+impl<T: Coord> RemAssign<T> for Point2<T> {
+    fn rem_assign(&mut self, other: T) {
+        self.x %= other;
+        self.y %= other;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
 
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
 pub struct Point3<T> {
@@ -182,6 +326,12 @@ impl<T> Point3<T> {
     pub const fn new(x: T, y: T, z: T) -> Self {
         Self { x, y, z }
     }
+
+    /// Applies `f` to every coordinate, e.g. to convert them with `as`.
+    #[inline]
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Point3<U> {
+        Point3::new(f(self.x), f(self.y), f(self.z))
+    }
 }
 
 impl<T: Display> Display for Point3<T> {
@@ -190,74 +340,599 @@ impl<T: Display> Display for Point3<T> {
     }
 }
 
-macro_rules! point3_impl {
-    ($t:ty) => {
-        impl Point3<$t> {
-            #[inline]
-            pub const fn zero() -> Self {
-                Self::new(0, 0, 0)
-            }
+impl<T: Coord> Point3<T> {
+    #[inline]
+    pub const fn zero() -> Self {
+        Self::new(T::ZERO, T::ZERO, T::ZERO)
+    }
 
-            #[inline]
-            pub const fn add(self, other: Self) -> Self {
-                Self::new(self.x + other.x, self.y + other.y, self.z + self.z)
-            }
+    #[inline]
+    pub fn scale(self, f: T) -> Self {
+        Self::new(self.x * f, self.y * f, self.z * f)
+    }
 
-            #[inline]
-            pub const fn scale(self, f: $t) -> Self {
-                Self::new(self.x * f, self.y * f, self.z * f)
-            }
+    /// The component-wise minimum. Note that `Ord::min` compares lexicographically instead.
+    #[inline]
+    pub fn component_min(self, other: Self) -> Self {
+        Self::new(
+            min(self.x, other.x),
+            min(self.y, other.y),
+            min(self.z, other.z),
+        )
+    }
 
-            #[inline]
-            pub const fn dist_squared(self, other: Self) -> $t {
-                let dx = self.x - other.x;
-                let dy = self.y - other.y;
-                let dz = self.z - other.z;
-                dx * dx + dy * dy + dz * dz
-            }
+    /// The component-wise maximum. Note that `Ord::max` compares lexicographically instead.
+    #[inline]
+    pub fn component_max(self, other: Self) -> Self {
+        Self::new(
+            max(self.x, other.x),
+            max(self.y, other.y),
+            max(self.z, other.z),
+        )
+    }
 
-            #[inline]
-            pub fn dist(self, other: Self) -> f64 {
-                (self.dist_squared(other) as f64).sqrt()
-            }
-        }
+    #[inline]
+    pub fn dist_squared(self, other: Self) -> T {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        let dz = abs_diff(self.z, other.z);
+        dx * dx + dy * dy + dz * dz
+    }
 
-        impl Add for Point3<$t> {
-            type Output = Self;
+    #[inline]
+    pub fn dist(self, other: Self) -> f64 {
+        self.dist_squared(other).as_f64().sqrt()
+    }
 
-            fn add(self, other: Self) -> Self {
-                self.add(other)
-            }
-        }
+    /// The number of orthogonal steps between the points.
+    #[inline]
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
 
-        impl AddAssign<Point3<$t>> for Point3<$t> {
-            fn add_assign(&mut self, other: Self) {
-                self.x += other.x;
-                self.y += other.y;
-                self.z += other.z;
-            }
-        }
+    /// The number of king moves, which may be diagonal, between the points.
+    #[inline]
+    pub fn chebyshev(self, other: Self) -> T {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        let dz = abs_diff(self.z, other.z);
+        max(max(dx, dy), dz)
+    }
+
+    #[inline]
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl<T: Signed> Point3<T> {
+    /// The cross product, which is perpendicular to both points.
+    #[inline]
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl Point3<usize> {
+    /// Moves a grid index by an offset, returns [`None`] if it would leave the
+    /// first octant.
+    #[inline]
+    pub fn checked_add_signed(self, offset: Point3<isize>) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add_signed(offset.x)?,
+            self.y.checked_add_signed(offset.y)?,
+            self.z.checked_add_signed(offset.z)?,
+        ))
+    }
+}
+
+impl TryFrom<Point3<isize>> for Point3<usize> {
+    type Error = TryFromIntError;
+
+    fn try_from(p: Point3<isize>) -> Result<Self, Self::Error> {
+        Ok(Self::new(p.x.try_into()?, p.y.try_into()?, p.z.try_into()?))
+    }
+}
+
+impl TryFrom<Point3<usize>> for Point3<isize> {
+    type Error = TryFromIntError;
+
+    fn try_from(p: Point3<usize>) -> Result<Self, Self::Error> {
+        Ok(Self::new(p.x.try_into()?, p.y.try_into()?, p.z.try_into()?))
+    }
+}
 
-        impl Mul<$t> for Point3<$t> {
-            type Output = Self;
+impl<T: Coord> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Coord> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl<T: Coord> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Coord> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
+impl<T: Signed> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Coord> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, other: T) -> Self {
+        self.scale(other)
+    }
+}
+
+impl<T: Coord> MulAssign<T> for Point3<T> {
+    fn mul_assign(&mut self, other: T) {
+        self.x *= other;
+        self.y *= other;
+        self.z *= other;
+    }
+}
+
+impl<T: Coord> Div<T> for Point3<T> {
+    type Output = Self;
+
+    fn div(self, other: T) -> Self {
+        Self::new(self.x / other, self.y / other, self.z / other)
+    }
+}
+
+impl<T: Coord> DivAssign<T> for Point3<T> {
+    fn div_assign(&mut self, other: T) {
+        self.x /= other;
+        self.y /= other;
+        self.z /= other;
+    }
+}
+
+impl<T: Coord> Rem<T> for Point3<T> {
+    type Output = Self;
+
+    fn rem(self, other: T) -> Self {
+        Self::new(self.x % other, self.y % other, self.z % other)
+    }
+}
 
-            fn mul(self, other: $t) -> Self {
-                self.scale(other)
+impl<T: Coord> RemAssign<T> for Point3<T> {
+    fn rem_assign(&mut self, other: T) {
+        self.x %= other;
+        self.y %= other;
+        self.z %= other;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The absolute difference, which doesn't underflow for unsigned coordinates.
+#[inline]
+fn abs_diff<T: Coord>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+/// `Ord::min` for coordinates that are only `PartialOrd`, like floats.
+#[inline]
+fn min<T: Coord>(a: T, b: T) -> T {
+    if b < a { b } else { a }
+}
+
+/// `Ord::max` for coordinates that are only `PartialOrd`, like floats.
+#[inline]
+fn max<T: Coord>(a: T, b: T) -> T {
+    if b > a { b } else { a }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential::{Gen, forall};
+
+    type P2 = (i64, i64);
+    type P3 = (i64, i64, i64);
+
+    /// A coordinate small enough that no tested expression overflows `$t`.
+    fn coord(g: &mut Gen, max: i64) -> i64 {
+        let bound = (max / 8).min(50);
+        g.signed(-bound..=bound)
+    }
+
+    /// A coordinate small enough that squared distances don't overflow `$t`.
+    fn small_coord(g: &mut Gen, max: i64) -> i64 {
+        let bound = ((max / 12).isqrt()).min(50);
+        g.signed(-bound..=bound)
+    }
+
+    macro_rules! point_tests {
+        ($($name:ident: $t:ty),*) => {$(
+            mod $name {
+                use super::*;
+
+                const MAX: i64 = <$t>::MAX as i64;
+
+                fn p2(g: &mut Gen) -> P2 {
+                    (coord(g, MAX), coord(g, MAX))
+                }
+
+                fn p3(g: &mut Gen) -> P3 {
+                    (coord(g, MAX), coord(g, MAX), coord(g, MAX))
+                }
+
+                fn scalar(g: &mut Gen) -> i64 {
+                    g.signed(-3..=3)
+                }
+
+                fn to2((x, y): P2) -> Point2<$t> {
+                    Point2::new(x as $t, y as $t)
+                }
+
+                fn to3((x, y, z): P3) -> Point3<$t> {
+                    Point3::new(x as $t, y as $t, z as $t)
+                }
+
+                #[test]
+                fn add_sub_neg() {
+                    forall(|g| (p2(g), p2(g)), |&(a, b)| {
+                        let (a, b) = (to2(a), to2(b));
+                        a + b == b + a
+                            && a + b - b == a
+                            && a - b == a + -b
+                            && -(-a) == a
+                            && a + Point2::<$t>::zero() == a
+                            && a - a == Point2::<$t>::zero()
+                    });
+                    forall(|g| (p3(g), p3(g)), |&(a, b)| {
+                        let (a, b) = (to3(a), to3(b));
+                        a + b == b + a
+                            && a + b - b == a
+                            && a - b == a + -b
+                            && -(-a) == a
+                            && a + Point3::<$t>::zero() == a
+                            && a - a == Point3::<$t>::zero()
+                    });
+                }
+
+                #[test]
+                fn operators_act_on_components() {
+                    forall(|g| (p2(g), p2(g), scalar(g)), |&(a, b, k)| {
+                        let (p, q, f) = (to2(a), to2(b), k as $t);
+                        p + q == to2((a.0 + b.0, a.1 + b.1))
+                            && p - q == to2((a.0 - b.0, a.1 - b.1))
+                            && p * f == to2((a.0 * k, a.1 * k))
+                            && (k == 0 || p / f == to2((a.0 / k, a.1 / k)))
+                            && (k == 0 || p % f == to2((a.0 % k, a.1 % k)))
+                    });
+                    forall(|g| (p3(g), p3(g), scalar(g)), |&(a, b, k)| {
+                        let (p, q, f) = (to3(a), to3(b), k as $t);
+                        p + q == to3((a.0 + b.0, a.1 + b.1, a.2 + b.2))
+                            && p - q == to3((a.0 - b.0, a.1 - b.1, a.2 - b.2))
+                            && p * f == to3((a.0 * k, a.1 * k, a.2 * k))
+                            && (k == 0 || p / f == to3((a.0 / k, a.1 / k, a.2 / k)))
+                            && (k == 0 || p % f == to3((a.0 % k, a.1 % k, a.2 % k)))
+                    });
+                }
+
+                #[test]
+                fn assignment_matches_operators() {
+                    forall(|g| (p2(g), p2(g), scalar(g)), |&(a, b, k)| {
+                        let (a, b, k) = (to2(a), to2(b), k as $t);
+                        let mut p = a;
+                        p += b;
+                        let mut ok = p == a + b;
+                        p = a;
+                        p -= b;
+                        ok &= p == a - b;
+                        p = a;
+                        p *= k;
+                        ok &= p == a * k;
+                        if k != 0 {
+                            p = a;
+                            p /= k;
+                            ok &= p == a / k;
+                            p = a;
+                            p %= k;
+                            ok &= p == a % k;
+                        }
+                        ok
+                    });
+                    forall(|g| (p3(g), p3(g), scalar(g)), |&(a, b, k)| {
+                        let (a, b, k) = (to3(a), to3(b), k as $t);
+                        let mut p = a;
+                        p += b;
+                        let mut ok = p == a + b;
+                        p = a;
+                        p -= b;
+                        ok &= p == a - b;
+                        p = a;
+                        p *= k;
+                        ok &= p == a * k;
+                        if k != 0 {
+                            p = a;
+                            p /= k;
+                            ok &= p == a / k;
+                            p = a;
+                            p %= k;
+                            ok &= p == a % k;
+                        }
+                        ok
+                    });
+                }
+
+                #[test]
+                fn division_and_remainder_recombine() {
+                    forall(|g| (p2(g), scalar(g)), |&(a, k)| {
+                        let (a, k) = (to2(a), k as $t);
+                        k == 0 || (a / k) * k + a % k == a && (a * k) / k == a
+                    });
+                    forall(|g| (p3(g), scalar(g)), |&(a, k)| {
+                        let (a, k) = (to3(a), k as $t);
+                        k == 0 || (a / k) * k + a % k == a && (a * k) / k == a
+                    });
+                }
+
+                #[test]
+                fn component_min_and_max() {
+                    forall(|g| (p2(g), p2(g)), |&(a, b)| {
+                        let (p, q) = (to2(a), to2(b));
+                        p.component_min(q) == to2((a.0.min(b.0), a.1.min(b.1)))
+                            && p.component_max(q) == to2((a.0.max(b.0), a.1.max(b.1)))
+                            && p.component_min(q) + p.component_max(q) == p + q
+                    });
+                    forall(|g| (p3(g), p3(g)), |&(a, b)| {
+                        let (p, q) = (to3(a), to3(b));
+                        p.component_min(q) == to3((a.0.min(b.0), a.1.min(b.1), a.2.min(b.2)))
+                            && p.component_max(q) == to3((a.0.max(b.0), a.1.max(b.1), a.2.max(b.2)))
+                            && p.component_min(q) + p.component_max(q) == p + q
+                    });
+                }
+
+                #[test]
+                fn distances_are_metrics() {
+                    forall(|g| (p2(g), p2(g), p2(g)), |&(a, b, c)| {
+                        let (a, b, c) = (to2(a), to2(b), to2(c));
+                        let m = |p: Point2<$t>, q: Point2<$t>| p.manhattan(q) as i64;
+                        let c8 = |p: Point2<$t>, q: Point2<$t>| p.chebyshev(q) as i64;
+                        m(a, b) == m(b, a)
+                            && (m(a, b) == 0) == (a == b)
+                            && m(a, c) <= m(a, b) + m(b, c)
+                            && m(a, b) == m(a - b, Point2::<$t>::zero())
+                            && c8(a, b) == c8(b, a)
+                            && c8(a, c) <= c8(a, b) + c8(b, c)
+                            && c8(a, b) <= m(a, b)
+                            && m(a, b) <= 2 * c8(a, b)
+                    });
+                    forall(|g| (p3(g), p3(g), p3(g)), |&(a, b, c)| {
+                        let (a, b, c) = (to3(a), to3(b), to3(c));
+                        let m = |p: Point3<$t>, q: Point3<$t>| p.manhattan(q) as i64;
+                        let c8 = |p: Point3<$t>, q: Point3<$t>| p.chebyshev(q) as i64;
+                        m(a, b) == m(b, a)
+                            && (m(a, b) == 0) == (a == b)
+                            && m(a, c) <= m(a, b) + m(b, c)
+                            && m(a, b) == m(a - b, Point3::<$t>::zero())
+                            && c8(a, b) == c8(b, a)
+                            && c8(a, c) <= c8(a, b) + c8(b, c)
+                            && c8(a, b) <= m(a, b)
+                            && m(a, b) <= 3 * c8(a, b)
+                    });
+                }
+
+                #[test]
+                fn dist_squared_matches_components() {
+                    let small2 = |g: &mut Gen| (small_coord(g, MAX), small_coord(g, MAX));
+                    forall(|g| (small2(g), small2(g)), |&(a, b)| {
+                        let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+                        let (p, q) = (to2(a), to2(b));
+                        p.dist_squared(q) as i64 == dx * dx + dy * dy
+                            && p.dist_squared(q) == (p - q).dist_squared(Point2::<$t>::zero())
+                    });
+                    let small3 =
+                        |g: &mut Gen| (small_coord(g, MAX), small_coord(g, MAX), small_coord(g, MAX));
+                    forall(|g| (small3(g), small3(g)), |&(a, b)| {
+                        let (dx, dy, dz) = (a.0 - b.0, a.1 - b.1, a.2 - b.2);
+                        let (p, q) = (to3(a), to3(b));
+                        p.dist_squared(q) as i64 == dx * dx + dy * dy + dz * dz
+                            && p.dist_squared(q) == (p - q).dist_squared(Point3::<$t>::zero())
+                    });
+                }
+
+                #[test]
+                fn headings() {
+                    let origin = Point2::<$t>::zero();
+                    assert_eq!(origin.up(), Point2::new(0, -1));
+                    assert_eq!(origin.right(), Point2::new(1, 0));
+                    assert_eq!(origin.down(), Point2::new(0, 1));
+                    assert_eq!(origin.left(), Point2::new(-1, 0));
+                    for heading in Heading::ALL {
+                        assert_eq!(Point2::<$t>::from(heading), Point2::<$t>::unit(heading));
+                        assert_eq!(Point2::<$t>::unit(Heading::North).rotate(heading), Point2::<$t>::unit(heading));
+                        assert_eq!(origin.step(heading), origin + Point2::<$t>::unit(heading));
+                        assert_eq!(Point2::<$t>::unit(heading.turn_right()), Point2::<$t>::unit(heading).rotate(Heading::East));
+                        assert_eq!(Point2::<$t>::unit(heading.reverse()), -Point2::<$t>::unit(heading));
+                        assert_eq!(Point2::<$t>::unit8(heading.into()), Point2::<$t>::unit(heading));
+                    }
+                    for heading in Heading8::ALL {
+                        let unit = Point2::<$t>::unit8(heading);
+                        assert_eq!(Point2::<$t>::from(heading), unit);
+                        assert_eq!(Point2::<$t>::dirs8()[heading.index()], unit);
+                        assert_eq!(origin.step8(heading.reverse()), -unit);
+                        assert_eq!(unit.manhattan(origin) == 2, heading.is_diagonal());
+                    }
+                    forall(|g| p2(g), |&a| {
+                        let a = to2(a);
+                        a.neighbors4().iter().all(|&n| a.manhattan(n) == 1)
+                            && a.neighbors8().iter().all(|&n| a.chebyshev(n) == 1)
+                            && a.rotate(Heading::East).rotate(Heading::West) == a
+                            && a.rotate(Heading::South) == -a
+                    });
+                }
             }
-        }
+        )*};
+    }
+
+    point_tests!(point_i8: i8, point_i16: i16, point_i32: i32, point_i64: i64, point_isize: isize);
+
+    macro_rules! unsigned_point_tests {
+        ($($name:ident: $t:ty),*) => {$(
+            mod $name {
+                use super::*;
 
-        impl MulAssign<$t> for Point3<$t> {
-            fn mul_assign(&mut self, other: $t) {
-                self.x *= other;
-                self.y *= other;
-                self.z *= other;
+                const MAX: u64 = <$t>::MAX as u64;
+
+                fn p2(g: &mut Gen) -> (u64, u64) {
+                    let bound = (MAX / 4).min(100);
+                    (g.range(0..=bound), g.range(0..=bound))
+                }
+
+                fn to2((x, y): (u64, u64)) -> Point2<$t> {
+                    Point2::new(x as $t, y as $t)
+                }
+
+                #[test]
+                fn arithmetic_and_distances_do_not_underflow() {
+                    forall(|g| (p2(g), p2(g)), |&(a, b)| {
+                        let (p, q) = (to2(a), to2(b));
+                        let (dx, dy) = (a.0.abs_diff(b.0), a.1.abs_diff(b.1));
+                        p + q - q == p
+                            && p.component_max(q) - p.component_min(q) == to2((dx, dy))
+                            && p.manhattan(q) as u64 == dx + dy
+                            && p.chebyshev(q) as u64 == dx.max(dy)
+                            && p.manhattan(q) == q.manhattan(p)
+                    });
+                }
             }
-        }
-    };
-}
+        )*};
+    }
+
+    unsigned_point_tests!(point_u8: u8, point_u16: u16, point_u32: u32, point_u64: u64, point_usize: usize);
+
+    #[test]
+    fn usable_in_consts() {
+        const ORIGIN: Point2<i64> = Point2::zero();
+        const DIRS: [Point2<i8>; 8] = Point2::dirs8();
+        const UP: Point2<f64> = Point2::unit(Heading::North);
+        assert_eq!(ORIGIN, Point2::new(0, 0));
+        assert_eq!(DIRS, Heading8::ALL.map(Point2::unit8));
+        assert_eq!(Point2::dirs4(), Heading::ALL.map(Point2::<i32>::unit));
+        assert_eq!(UP, Point2::new(0.0, -1.0));
+    }
+
+    #[test]
+    fn float_coordinates() {
+        let (p, q) = (Point2::new(0.5, -1.0), Point2::new(3.5, 3.0));
+        assert_eq!(p + q, Point2::new(4.0, 2.0));
+        assert_eq!(q * 2.0, Point2::new(7.0, 6.0));
+        assert_eq!(p.dist(q), 5.0);
+        assert_eq!(p.manhattan(q), 7.0);
+        assert_eq!(p.component_min(q), Point2::new(0.5, -1.0));
+        assert_eq!(Point2::<f64>::unit(Heading::West), Point2::new(-1.0, 0.0));
+        let (p, q) = (Point3::new(1.0f32, 2.0, 2.0), Point3::zero());
+        assert_eq!(p.dist(q), 3.0);
+        assert_eq!(-p, Point3::new(-1.0, -2.0, -2.0));
+    }
+
+    #[test]
+    fn converts_between_indices_and_offsets() {
+        let index = Point2::new(3usize, 0);
+        let offset = Point2::<isize>::try_from(index).unwrap();
+        assert_eq!(offset, Point2::new(3, 0));
+        assert_eq!(
+            Point2::<usize>::try_from(offset.down()),
+            Ok(Point2::new(3, 1))
+        );
+        assert!(Point2::<usize>::try_from(offset.up()).is_err());
+        assert!(Point2::<isize>::try_from(Point2::new(usize::MAX, 0)).is_err());
+        assert_eq!(
+            index.checked_add_signed(Point2::unit(Heading::South)),
+            Some(Point2::new(3, 1))
+        );
+        assert_eq!(index.checked_add_signed(Point2::unit(Heading::North)), None);
+        let neighbors: Vec<_> = Point2::<isize>::dirs4()
+            .iter()
+            .filter_map(|&d| index.checked_add_signed(d))
+            .collect();
+        assert_eq!(
+            neighbors,
+            [Point2::new(4, 0), Point2::new(3, 1), Point2::new(2, 0)]
+        );
+        assert_eq!(
+            Point3::<usize>::try_from(Point3::new(1isize, 2, 3)),
+            Ok(Point3::new(1, 2, 3))
+        );
+        assert_eq!(
+            Point3::new(1usize, 1, 1).checked_add_signed(Point3::new(0, -2, 0)),
+            None
+        );
+        assert_eq!(Point2::new(1i32, -2).map(f64::from), Point2::new(1.0, -2.0));
+    }
 
-point3_impl!(i8);
-point3_impl!(i16);
-point3_impl!(i32);
-point3_impl!(i64);
-point3_impl!(isize);
+    #[test]
+    fn point3_add_uses_both_z() {
+        let p = Point3::new(1, 2, 3) + Point3::new(10, 20, 30);
+        assert_eq!(p, Point3::new(11, 22, 33));
+        let mut q = Point3::new(1i8, 2, 3);
+        q += Point3::new(1, 1, 1);
+        assert_eq!(q, Point3::new(2, 3, 4));
+    }
+
+    #[test]
+    fn displays_coordinates() {
+        assert_eq!(Point2::new(3, -4).to_string(), "(3, -4)");
+        assert_eq!(Point3::new(1, 2, 3).to_string(), "(1, 2, 3)");
+    }
+
+    #[test]
+    fn distances() {
+        let (p, q) = (Point2::new(1i64, 2), Point2::new(4, -2));
+        assert_eq!(p.manhattan(q), 7);
+        assert_eq!(p.chebyshev(q), 4);
+        assert_eq!(p.dist(q), 5.0);
+        let (p, q) = (Point3::new(0i32, 0, 0), Point3::new(-1, 2, -3));
+        assert_eq!(p.manhattan(q), 6);
+        assert_eq!(p.chebyshev(q), 3);
+        assert_eq!(p.dist_squared(q), 14);
+    }
+
+    #[test]
+    fn dirs8_walks_clockwise_from_north() {
+        let dirs = Point2::<i32>::dirs8();
+        assert_eq!(dirs[0], Point2::new(0, -1));
+        assert_eq!(dirs[1], Point2::new(1, -1));
+        assert_eq!(dirs[4], Point2::new(0, 1));
+        assert_eq!(dirs[7], Point2::new(-1, -1));
+        assert_eq!(
+            &Point2::<i32>::dirs4()[..],
+            &[dirs[0], dirs[2], dirs[4], dirs[6]]
+        );
+    }
+}
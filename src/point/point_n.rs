@@ -0,0 +1,345 @@
+use std::array;
+use std::fmt::Display;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Rem, RemAssign, Sub,
+    SubAssign,
+};
+
+use super::{Coord, Point2, Point3, Signed, abs_diff, max, min};
+
+/// A point with any number of dimensions, for puzzles that go beyond three.
+///
+/// ```
+/// # use advent_of_code::point::PointN;
+/// let p = PointN::new([0, 0, 0, 0]);
+/// assert_eq!(p.orthogonal_neighbors().count(), 8);
+/// assert_eq!(p.moore_neighbors().count(), 80);
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub struct PointN<T, const D: usize>(pub [T; D]);
+
+impl<T, const D: usize> PointN<T, D> {
+    #[inline]
+    pub const fn new(coords: [T; D]) -> Self {
+        Self(coords)
+    }
+
+    /// Applies `f` to every coordinate, e.g. to convert them with `as`.
+    #[inline]
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> PointN<U, D> {
+        PointN(self.0.map(f))
+    }
+}
+
+impl<T: Display, const D: usize> Display for PointN<T, D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("(")?;
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{c}")?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl<T: Coord, const D: usize> PointN<T, D> {
+    #[inline]
+    pub fn zero() -> Self {
+        Self([T::ZERO; D])
+    }
+
+    #[inline]
+    pub fn scale(self, f: T) -> Self {
+        self.map(|c| c * f)
+    }
+
+    /// The component-wise minimum. Note that `Ord::min` compares lexicographically instead.
+    #[inline]
+    pub fn component_min(self, other: Self) -> Self {
+        self.zip(other, min)
+    }
+
+    /// The component-wise maximum. Note that `Ord::max` compares lexicographically instead.
+    #[inline]
+    pub fn component_max(self, other: Self) -> Self {
+        self.zip(other, max)
+    }
+
+    #[inline]
+    pub fn dist_squared(self, other: Self) -> T {
+        self.zip(other, abs_diff)
+            .0
+            .into_iter()
+            .fold(T::ZERO, |sum, d| sum + d * d)
+    }
+
+    #[inline]
+    pub fn dist(self, other: Self) -> f64 {
+        self.dist_squared(other).as_f64().sqrt()
+    }
+
+    /// The number of orthogonal steps between the points.
+    #[inline]
+    pub fn manhattan(self, other: Self) -> T {
+        self.zip(other, abs_diff)
+            .0
+            .into_iter()
+            .fold(T::ZERO, |sum, d| sum + d)
+    }
+
+    /// The number of king moves, which may be diagonal, between the points.
+    #[inline]
+    pub fn chebyshev(self, other: Self) -> T {
+        self.zip(other, abs_diff).0.into_iter().fold(T::ZERO, max)
+    }
+
+    #[inline]
+    fn zip(self, other: Self, mut f: impl FnMut(T, T) -> T) -> Self {
+        Self(array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+}
+
+impl<T: Signed, const D: usize> PointN<T, D> {
+    /// The `2 * D` unit vectors along the axes: the negative then the positive
+    /// direction of the first axis, then of the second, and so on.
+    pub fn orthogonal_dirs() -> impl Iterator<Item = Self> {
+        (0..D).flat_map(|axis| {
+            [-T::ONE, T::ONE].map(|c| {
+                let mut dir = Self::zero();
+                dir.0[axis] = c;
+                dir
+            })
+        })
+    }
+
+    /// The `3^D - 1` vectors with every coordinate in `-1..=1`, except the zero vector.
+    pub fn moore_dirs() -> impl Iterator<Item = Self> {
+        let count = 3usize.pow(D as u32);
+        (0..count).filter(move |&i| i != count / 2).map(|mut i| {
+            Self(array::from_fn(|_| {
+                let c = [-T::ONE, T::ZERO, T::ONE][i % 3];
+                i /= 3;
+                c
+            }))
+        })
+    }
+
+    /// The points one step away along an axis: 4 in 2D, 6 in 3D.
+    pub fn orthogonal_neighbors(self) -> impl Iterator<Item = Self> {
+        Self::orthogonal_dirs().map(move |d| self + d)
+    }
+
+    /// The points touching this one, including diagonally: 8 in 2D, 26 in 3D, 80 in 4D.
+    pub fn moore_neighbors(self) -> impl Iterator<Item = Self> {
+        Self::moore_dirs().map(move |d| self + d)
+    }
+}
+
+impl<T, const D: usize> Index<usize> for PointN<T, D> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.0[axis]
+    }
+}
+
+impl<T, const D: usize> IndexMut<usize> for PointN<T, D> {
+    fn index_mut(&mut self, axis: usize) -> &mut T {
+        &mut self.0[axis]
+    }
+}
+
+impl<T, const D: usize> From<[T; D]> for PointN<T, D> {
+    fn from(coords: [T; D]) -> Self {
+        Self(coords)
+    }
+}
+
+impl<T> From<Point2<T>> for PointN<T, 2> {
+    fn from(p: Point2<T>) -> Self {
+        Self([p.x, p.y])
+    }
+}
+
+impl<T> From<PointN<T, 2>> for Point2<T> {
+    fn from(PointN([x, y]): PointN<T, 2>) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point3<T>> for PointN<T, 3> {
+    fn from(p: Point3<T>) -> Self {
+        Self([p.x, p.y, p.z])
+    }
+}
+
+impl<T> From<PointN<T, 3>> for Point3<T> {
+    fn from(PointN([x, y, z]): PointN<T, 3>) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T: Coord, const D: usize> Add for PointN<T, D> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.zip(other, |a, b| a + b)
+    }
+}
+
+impl<T: Coord, const D: usize> AddAssign for PointN<T, D> {
+    fn add_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0) {
+            *a += b;
+        }
+    }
+}
+
+impl<T: Coord, const D: usize> Sub for PointN<T, D> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip(other, |a, b| a - b)
+    }
+}
+
+impl<T: Coord, const D: usize> SubAssign for PointN<T, D> {
+    fn sub_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0) {
+            *a -= b;
+        }
+    }
+}
+
+impl<T: Signed, const D: usize> Neg for PointN<T, D> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|c| -c)
+    }
+}
+
+impl<T: Coord, const D: usize> Mul<T> for PointN<T, D> {
+    type Output = Self;
+
+    fn mul(self, other: T) -> Self {
+        self.scale(other)
+    }
+}
+
+impl<T: Coord, const D: usize> MulAssign<T> for PointN<T, D> {
+    fn mul_assign(&mut self, other: T) {
+        self.0.iter_mut().for_each(|c| *c *= other);
+    }
+}
+
+impl<T: Coord, const D: usize> Div<T> for PointN<T, D> {
+    type Output = Self;
+
+    fn div(self, other: T) -> Self {
+        self.map(|c| c / other)
+    }
+}
+
+impl<T: Coord, const D: usize> DivAssign<T> for PointN<T, D> {
+    fn div_assign(&mut self, other: T) {
+        self.0.iter_mut().for_each(|c| *c /= other);
+    }
+}
+
+impl<T: Coord, const D: usize> Rem<T> for PointN<T, D> {
+    type Output = Self;
+
+    fn rem(self, other: T) -> Self {
+        self.map(|c| c % other)
+    }
+}
+
+impl<T: Coord, const D: usize> RemAssign<T> for PointN<T, D> {
+    fn rem_assign(&mut self, other: T) {
+        self.0.iter_mut().for_each(|c| *c %= other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::differential::forall;
+
+    fn check_neighbors<const D: usize>(orthogonal: usize, moore: usize) {
+        let p = PointN::new(array::from_fn::<i64, D, _>(|i| i as i64 * 7 - 3));
+        let ortho: HashSet<_> = p.orthogonal_neighbors().collect();
+        let all: HashSet<_> = p.moore_neighbors().collect();
+        assert_eq!(ortho.len(), orthogonal);
+        assert_eq!(all.len(), moore);
+        assert!(ortho.iter().all(|&n| p.manhattan(n) == 1));
+        assert!(all.iter().all(|&n| p.chebyshev(n) == 1));
+        assert!(ortho.is_subset(&all));
+    }
+
+    #[test]
+    fn counts_neighbors_in_any_dimension() {
+        check_neighbors::<1>(2, 2);
+        check_neighbors::<2>(4, 8);
+        check_neighbors::<3>(6, 26);
+        check_neighbors::<4>(8, 80);
+        check_neighbors::<5>(10, 242);
+    }
+
+    #[test]
+    fn matches_point2_neighbors() {
+        let p = Point2::new(5i32, -2);
+        let sorted = |mut v: Vec<Point2<i32>>| {
+            v.sort();
+            v
+        };
+        let ortho = PointN::from(p).orthogonal_neighbors().map(Point2::from);
+        let moore = PointN::from(p).moore_neighbors().map(Point2::from);
+        assert_eq!(sorted(ortho.collect()), sorted(p.neighbors4().to_vec()));
+        assert_eq!(sorted(moore.collect()), sorted(p.neighbors8().to_vec()));
+    }
+
+    #[test]
+    fn matches_point3_arithmetic() {
+        let point = |g: &mut crate::differential::Gen| {
+            (
+                g.signed(-100..=100),
+                g.signed(-100..=100),
+                g.signed(-100..=100),
+            )
+        };
+        forall(
+            |g| (point(g), point(g), g.signed(1..=5)),
+            |&((ax, ay, az), (bx, by, bz), k)| {
+                let (a, b) = (Point3::new(ax, ay, az), Point3::new(bx, by, bz));
+                let (m, n) = (PointN::from(a), PointN::from(b));
+                Point3::from(m + n) == a + b
+                    && Point3::from(m - n) == a - b
+                    && Point3::from(-m) == -a
+                    && Point3::from(m * k) == a * k
+                    && Point3::from(m / k) == a / k
+                    && Point3::from(m % k) == a % k
+                    && Point3::from(m.component_min(n)) == a.component_min(b)
+                    && Point3::from(m.component_max(n)) == a.component_max(b)
+                    && m.manhattan(n) == a.manhattan(b)
+                    && m.chebyshev(n) == a.chebyshev(b)
+                    && m.dist_squared(n) == a.dist_squared(b)
+            },
+        );
+    }
+
+    #[test]
+    fn indexes_and_displays_coordinates() {
+        let mut p = PointN::new([1u8, 2, 3, 4]);
+        p[3] = 9;
+        assert_eq!(p[0], 1);
+        assert_eq!(p.to_string(), "(1, 2, 3, 9)");
+        p += PointN::from([1, 1, 1, 1]);
+        assert_eq!(p, PointN([2, 3, 4, 10]));
+        assert_eq!(p.manhattan(PointN([4, 3, 2, 0])), 14);
+    }
+}
@@ -0,0 +1,409 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::gf2::BitVec;
+use crate::point::{Heading, Point2};
+
+type Point = Point2<i32>;
+
+/// A set of cells on a square grid, normalized so that its bounding box
+/// starts at the origin and its cells are sorted top-to-bottom, left-to-right.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Polyomino {
+    cells: Vec<Point>,
+}
+
+impl Polyomino {
+    pub fn new(cells: impl IntoIterator<Item = Point>) -> Self {
+        let mut cells: Vec<Point> = cells.into_iter().collect();
+        let xmin = cells.iter().map(|p| p.x).min().unwrap_or(0);
+        let ymin = cells.iter().map(|p| p.y).min().unwrap_or(0);
+        for p in cells.iter_mut() {
+            *p = Point::new(p.x - xmin, p.y - ymin);
+        }
+        cells.sort_unstable_by_key(|p| (p.y, p.x));
+        cells.dedup();
+        Self { cells }
+    }
+
+    #[inline]
+    pub fn cells(&self) -> &[Point] {
+        &self.cells
+    }
+
+    #[inline]
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.iter().map(|p| p.x + 1).max().unwrap_or(0) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.iter().map(|p| p.y + 1).max().unwrap_or(0) as usize
+    }
+
+    /// Rotates the shape so that its top edge faces `heading`.
+    pub fn rotate(&self, heading: Heading) -> Self {
+        Self::new(self.cells.iter().map(|p| p.rotate(heading)))
+    }
+
+    /// Mirrors the shape left-to-right.
+    pub fn flip(&self) -> Self {
+        Self::new(self.cells.iter().map(|p| Point::new(-p.x, p.y)))
+    }
+
+    /// Every distinct rotation and reflection of the shape.
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut orientations: Vec<Self> = vec![];
+        for shape in [self.clone(), self.flip()] {
+            for heading in Heading::ALL {
+                let rotated = shape.rotate(heading);
+                if !orientations.contains(&rotated) {
+                    orientations.push(rotated);
+                }
+            }
+        }
+        orientations
+    }
+}
+
+impl Display for Polyomino {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height() as i32 {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width() as i32 {
+                let c = if self.cells.contains(&Point::new(x, y)) {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses a picture of a shape where `#` marks a cell and `.` is empty.
+impl FromStr for Polyomino {
+    type Err = ParsePolyominoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = vec![];
+        for (y, line) in s.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    '#' => cells.push(Point::new(x as i32, y as i32)),
+                    '.' => {}
+                    _ => return Err(ParsePolyominoError(c)),
+                }
+            }
+        }
+        Ok(Self::new(cells))
+    }
+}
+
+/// An error which can be returned when parsing a [`Polyomino`].
+#[derive(Debug)]
+pub struct ParsePolyominoError(char);
+
+impl Error for ParsePolyominoError {}
+
+impl Display for ParsePolyominoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unexpected character {:?} in shape", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The largest region, in cells, that [`can_pack`] searches.
+pub const MAX_SEARCH_AREA: usize = 64 * 64;
+
+/// Decides whether `counts[i]` copies of each `shapes[i]` can be placed in a
+/// `width` by `height` region without overlapping. Shapes may be rotated and
+/// reflected, and cells may be left empty.
+///
+/// Two sound shortcuts are tried first: the shapes cannot fit if their total
+/// area exceeds the region, and they trivially fit if every shape can be given
+/// its own bounding box. Only regions that fall between the two are searched,
+/// and returns [`None`] if such a region has more than [`MAX_SEARCH_AREA`]
+/// cells or its area doesn't fit in a `usize`.
+pub fn can_pack(
+    width: usize,
+    height: usize,
+    shapes: &[Polyomino],
+    counts: &[usize],
+) -> Option<bool> {
+    assert_eq!(shapes.len(), counts.len(), "expected one count per shape");
+
+    let area = width.checked_mul(height)?;
+    let needed = shapes
+        .iter()
+        .zip(counts)
+        .map(|(s, n)| s.area().saturating_mul(*n))
+        .fold(0, usize::saturating_add);
+    if needed > area {
+        return Some(false);
+    }
+
+    let total = counts
+        .iter()
+        .fold(0usize, |total, n| total.saturating_add(*n));
+    let bw = shapes.iter().map(Polyomino::width).max().unwrap_or(0);
+    let bh = shapes.iter().map(Polyomino::height).max().unwrap_or(0);
+    let boxes = |w: usize, h: usize| (width / w.max(1)) * (height / h.max(1));
+    if total <= boxes(bw, bh).max(boxes(bh, bw)) {
+        return Some(true);
+    }
+    if area > MAX_SEARCH_AREA {
+        return None;
+    }
+
+    let mut packer = Packer::new(width, height, shapes, counts);
+    Some(packer.search(area - needed))
+}
+
+/// A single way of putting a shape down in the region.
+struct Placement {
+    shape: usize,
+    cells: Vec<usize>,
+}
+
+/// Backtracking search using the heuristic from Algorithm X. Each step picks the
+/// empty cell with the fewest placements that could still cover it, then
+/// either covers it with one of those or leaves it empty, which spends one
+/// unit of slack. Cells nothing can cover are left empty up front. Identical
+/// shapes are tracked by count so their permutations are never explored.
+///
+/// Cells left empty are marked on the board, so the board and the remaining
+/// counts fully determine a subproblem. Ones that failed are remembered, up to
+/// mirroring the region, since different placement orders often reach the
+/// same board.
+struct Packer {
+    width: usize,
+    height: usize,
+    board: BitVec,
+    placements: Vec<Placement>,
+    /// Placements that cover the given cell.
+    covering: Vec<Vec<usize>>,
+    /// How many of the placement's cells are taken.
+    blocked: Vec<usize>,
+    counts: Vec<usize>,
+    remaining: usize,
+    failed: HashSet<(BitVec, Vec<usize>)>,
+}
+
+impl Packer {
+    fn new(width: usize, height: usize, shapes: &[Polyomino], counts: &[usize]) -> Self {
+        let size = width * height;
+        let mut placements = vec![];
+        let mut covering = vec![vec![]; size];
+
+        for (shape, polyomino) in shapes.iter().enumerate() {
+            for o in polyomino.orientations() {
+                let (w, h) = (o.width(), o.height());
+                if w > width || h > height {
+                    continue;
+                }
+                for y in 0..=height - h {
+                    for x in 0..=width - w {
+                        let cells: Vec<usize> = o
+                            .cells
+                            .iter()
+                            .map(|p| (y + p.y as usize) * width + x + p.x as usize)
+                            .collect();
+                        for &c in &cells {
+                            covering[c].push(placements.len());
+                        }
+                        placements.push(Placement { shape, cells });
+                    }
+                }
+            }
+        }
+
+        Self {
+            width,
+            height,
+            board: BitVec::zeros(size),
+            blocked: vec![0; placements.len()],
+            placements,
+            covering,
+            counts: counts.to_vec(),
+            remaining: counts.iter().sum(),
+            failed: HashSet::new(),
+        }
+    }
+
+    fn search(&mut self, slack: usize) -> bool {
+        if self.remaining == 0 {
+            return true;
+        }
+
+        // how many placements could still cover each cell
+        let mut live = vec![0usize; self.board.len()];
+        for (i, placement) in self.placements.iter().enumerate() {
+            if self.fits(i) {
+                for &c in &placement.cells {
+                    live[c] += 1;
+                }
+            }
+        }
+        let dead: Vec<usize> = (0..self.board.len())
+            .filter(|&c| live[c] == 0 && !self.board.get(c))
+            .collect();
+        let Some(cell) = (0..self.board.len())
+            .filter(|&c| live[c] > 0)
+            .min_by_key(|&c| live[c])
+        else {
+            return false;
+        };
+        if dead.len() > slack {
+            return false;
+        }
+
+        // marking the dead cells first lets more boards share a key
+        for &c in &dead {
+            self.take(c);
+        }
+        let key = (self.canonical_board(), self.counts.clone());
+        let found = !self.failed.contains(&key) && self.branch(cell, slack - dead.len());
+        if !found {
+            self.failed.insert(key);
+        }
+        for &c in &dead {
+            self.free(c);
+        }
+        found
+    }
+
+    fn branch(&mut self, cell: usize, slack: usize) -> bool {
+        let live: Vec<usize> = self.covering[cell]
+            .iter()
+            .copied()
+            .filter(|&i| self.fits(i))
+            .collect();
+        for i in live {
+            let shape = self.placements[i].shape;
+            for k in 0..self.placements[i].cells.len() {
+                self.take(self.placements[i].cells[k]);
+            }
+            self.counts[shape] -= 1;
+            self.remaining -= 1;
+            let found = self.search(slack);
+            for k in 0..self.placements[i].cells.len() {
+                self.free(self.placements[i].cells[k]);
+            }
+            self.counts[shape] += 1;
+            self.remaining += 1;
+            if found {
+                return true;
+            }
+        }
+
+        if slack > 0 {
+            self.take(cell);
+            let found = self.search(slack - 1);
+            self.free(cell);
+            return found;
+        }
+        false
+    }
+
+    fn take(&mut self, cell: usize) {
+        self.board.set(cell, true);
+        for &i in &self.covering[cell] {
+            self.blocked[i] += 1;
+        }
+    }
+
+    fn free(&mut self, cell: usize) {
+        self.board.set(cell, false);
+        for &i in &self.covering[cell] {
+            self.blocked[i] -= 1;
+        }
+    }
+
+    /// The smallest of the board's mirror images, which all lead to the same
+    /// answer since shapes may be reflected.
+    fn canonical_board(&self) -> BitVec {
+        let (w, h) = (self.width, self.height);
+        let mirror = |flip_x: bool, flip_y: bool| {
+            BitVec::from_indices(
+                self.board.len(),
+                self.board.ones().map(|c| {
+                    let (x, y) = (c % w, c / w);
+                    let x = if flip_x { w - 1 - x } else { x };
+                    let y = if flip_y { h - 1 - y } else { y };
+                    y * w + x
+                }),
+            )
+        };
+        [mirror(true, false), mirror(false, true), mirror(true, true)]
+            .into_iter()
+            .fold(self.board.clone(), Ord::min)
+    }
+
+    /// Whether we still have the placement's shape and all of its cells are empty.
+    #[inline]
+    fn fits(&self, placement: usize) -> bool {
+        self.blocked[placement] == 0 && self.counts[self.placements[placement].shape] > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orientations_are_distinct() {
+        let square: Polyomino = "##\n##".parse().unwrap();
+        assert_eq!(square.orientations().len(), 1);
+        let ell: Polyomino = "#.\n#.\n##".parse().unwrap();
+        assert_eq!(ell.orientations().len(), 8);
+        let tee: Polyomino = "###\n.#.".parse().unwrap();
+        assert_eq!(tee.orientations().len(), 4);
+    }
+
+    #[test]
+    fn rotate_and_flip() {
+        let ell: Polyomino = "#.\n#.\n##".parse().unwrap();
+        assert_eq!(ell.rotate(Heading::East).to_string(), "###\n#..");
+        assert_eq!(ell.flip().to_string(), ".#\n.#\n##");
+        assert_eq!(ell.rotate(Heading::South).rotate(Heading::South), ell);
+    }
+
+    #[test]
+    fn packs_exactly() {
+        let ell: Polyomino = "#.\n#.\n##".parse().unwrap();
+        // two L-tetrominoes tile a 2x4 region but not an 8x1 strip
+        assert_eq!(can_pack(2, 4, std::slice::from_ref(&ell), &[2]), Some(true));
+        assert_eq!(
+            can_pack(8, 1, std::slice::from_ref(&ell), &[2]),
+            Some(false)
+        );
+        // four only fit a 4x4 region by interlocking, with no room to spare
+        assert_eq!(can_pack(4, 4, std::slice::from_ref(&ell), &[4]), Some(true));
+        assert_eq!(can_pack(3, 5, &[ell], &[4]), Some(false));
+    }
+
+    #[test]
+    fn refuses_huge_regions() {
+        let ell: Polyomino = "#.\n#.\n##".parse().unwrap();
+        let ell = std::slice::from_ref(&ell);
+        assert_eq!(can_pack(usize::MAX, 2, ell, &[1]), None);
+        // the shortcuts still answer regions of any size
+        assert_eq!(can_pack(100_000, 100_000, ell, &[1]), Some(true));
+        assert_eq!(can_pack(100_000, 100_000, ell, &[usize::MAX]), Some(false));
+        // but too many shapes for their own boxes would need a search
+        assert_eq!(can_pack(100_000, 100_000, ell, &[2_000_000_000]), None);
+    }
+}
@@ -1,5 +1,26 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{process, thread};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::Selection;
+use crate::template::run_multi::run_multi;
+use crate::template::timings::Timings;
+
+/// Runs the selected days (all by default), `jobs` at a time. Zero uses one job per available core.
+pub fn handle(selection: Option<Selection>, is_release: bool, timeout: Option<u64>, jobs: usize) {
+    let jobs = match jobs {
+        0 => thread::available_parallelism().map_or(1, usize::from),
+        n => n,
+    };
+
+    let days_to_run = selection
+        .unwrap_or_else(Selection::all)
+        .resolve(&Timings::read_from_file());
+
+    if days_to_run.is_empty() {
+        println!("No days match the selection.");
+        return;
+    }
+
+    if run_multi(&days_to_run, is_release, false, timeout, jobs).has_failures() {
+        process::exit(1);
+    }
 }
@@ -1,8 +1,83 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::timings::Timings;
+use crate::template::watch::watch;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, InputSource, Selection};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+/// Solves the selected days one after another, or keeps re-running a single day with `watch`.
+pub fn handle(
+    selection: Selection,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    timeout: Option<u64>,
+    input: &InputSource,
+    is_watch: bool,
+) {
+    let days = selection.resolve(&Timings::read_from_file());
+
+    if days.is_empty() {
+        println!("No days match the selection.");
+        return;
+    }
+
+    if is_watch {
+        let [day] = days.into_iter().collect::<Vec<_>>()[..] else {
+            eprintln!("Can only watch a single day at a time.");
+            process::exit(1);
+        };
+        watch(day, release, timeout, input);
+    }
+
+    if *input == InputSource::Stdin && days.len() > 1 {
+        eprintln!("Can only read a single day from standard input.");
+        process::exit(1);
+    }
+
+    if submit_part.is_some() && days.len() > 1 {
+        eprintln!(
+            "Can only submit a single day at a time, but {} days match.",
+            days.len()
+        );
+        process::exit(1);
+    }
+
+    let mut failures: Vec<String> = vec![];
+
+    for (i, &day) in days.iter().enumerate() {
+        if days.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
+        }
+        if !solve_day(day, release, dhat, submit_part, timeout, input) {
+            failures.push(format!("day {day}"));
+        }
+    }
+
+    if !failures.is_empty() {
+        if days.len() > 1 {
+            println!(
+                "\n{ANSI_BOLD}Failed ({}):{ANSI_RESET} {}",
+                failures.len(),
+                failures.join(", ")
+            );
+        }
+        process::exit(1);
+    }
+}
+
+/// Runs a single day and returns whether it exited successfully.
+fn solve_day(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    timeout: Option<u64>,
+    input: &InputSource,
+) -> bool {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -23,6 +98,13 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(timeout) = timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(timeout.to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -30,5 +112,5 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    cmd.wait().unwrap().success()
 }
@@ -1,27 +1,27 @@
-use std::collections::HashSet;
+use std::process;
 
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{Selection, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(selection: Option<Selection>, run_all: bool, store: bool, timeout: Option<u64>) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
-    );
+    let selection = match selection {
+        Some(selection) => selection,
+        None if run_all => Selection::all(),
+        // when neither days nor the `--all` flag are given, skip days that are fully benched.
+        None => Selection::unsolved(),
+    };
+    let days_to_run = selection.resolve(&stored_timings);
+
+    if days_to_run.is_empty() {
+        println!("No days match the selection.");
+        return;
+    }
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // benchmarks run one day at a time so that days do not compete for cores.
+    let timings = run_multi(&days_to_run, true, true, timeout, 1);
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
@@ -37,4 +37,8 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if timings.has_failures() {
+        process::exit(1);
+    }
 }
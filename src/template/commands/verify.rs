@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::template::run_multi::child_commands::{parse_answers, run_solution};
+use crate::template::timings::Timings;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Selection};
+
+/// An input file together with the file holding its expected answers.
+struct InputSet {
+    name: String,
+    input: PathBuf,
+    answers: PathBuf,
+}
+
+/// Runs every selected day (all by default) against each of its input sets
+/// and compares the answers with the expected ones.
+pub fn handle(selection: Option<Selection>, is_release: bool, timeout: Option<u64>) {
+    let days = selection
+        .unwrap_or_else(Selection::all)
+        .resolve(&Timings::read_from_file());
+
+    let mut failures: Vec<String> = vec![];
+    let mut need_space = false;
+
+    for day in days {
+        let sets = input_sets(day);
+        if sets.is_empty() {
+            continue;
+        }
+
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let width = sets.iter().map(|set| set.name.len()).max().unwrap_or(0);
+
+        for set in sets {
+            let input = set.input.display().to_string();
+            let output =
+                run_solution(day, false, is_release, timeout, &["--input", &input], false).unwrap();
+
+            let answers = parse_answers(&output.lines);
+            let label = format!("{:width$}", set.name);
+
+            let Ok(expected) = fs::read_to_string(&set.answers) else {
+                let got: Vec<String> = answers
+                    .iter()
+                    .map(|(part, answer)| format!("part {part}: {answer}"))
+                    .collect();
+                println!(
+                    "{label}  ? {ANSI_ITALIC}no {} yet, got {}{ANSI_RESET}",
+                    set.answers.display(),
+                    got.join(", ")
+                );
+                if !output.success {
+                    output.stderr.iter().for_each(|line| eprintln!("{line}"));
+                    failures.push(format!("day {day} {}", set.name));
+                }
+                continue;
+            };
+
+            let expected = parse_answers(&expected.lines().map(String::from).collect::<Vec<_>>());
+            let mut mismatches = compare(&expected, &answers);
+            if expected.is_empty() {
+                mismatches.push(format!("no answers in {}", set.answers.display()));
+            }
+            if !output.success {
+                // a crash after the listed parts would otherwise go unnoticed
+                mismatches.push("the solution exited with an error".to_string());
+            }
+
+            if mismatches.is_empty() {
+                println!("{label}  ✓ {} answers match", expected.len());
+            } else {
+                println!("{label}  ✖ {}", mismatches.join(", "));
+                output.stderr.iter().for_each(|line| eprintln!("{line}"));
+                failures.push(format!("day {day} {}", set.name));
+            }
+        }
+    }
+
+    if !need_space {
+        println!("No input sets found for the selected days.");
+    } else if !failures.is_empty() {
+        println!(
+            "\n{ANSI_BOLD}Failed ({}):{ANSI_RESET} {}",
+            failures.len(),
+            failures.join(", ")
+        );
+        process::exit(1);
+    }
+}
+
+/// Describes every expected answer that differs from the actual one.
+fn compare(expected: &BTreeMap<u8, String>, actual: &BTreeMap<u8, String>) -> Vec<String> {
+    expected
+        .iter()
+        .filter_map(|(part, want)| match actual.get(part) {
+            Some(got) if got == want => None,
+            Some(got) => Some(format!("part {part}: expected {want}, got {got}")),
+            None => Some(format!("part {part}: expected {want}, got nothing")),
+        })
+        .collect()
+}
+
+/// The puzzle input `data/inputs/<day>.txt`, named `default`, followed by
+/// every `data/inputs/<day>/<name>.txt` in alphabetical order. The expected
+/// answers live next to each input as `<day>.answers` or `<name>.answers`.
+fn input_sets(day: Day) -> Vec<InputSet> {
+    let mut sets = vec![];
+
+    let default = PathBuf::from(format!("./data/inputs/{day}.txt"));
+    if default.is_file() {
+        sets.push(InputSet {
+            name: "default".into(),
+            answers: default.with_extension("answers"),
+            input: default,
+        });
+    }
+
+    let mut named: Vec<PathBuf> = fs::read_dir(format!("./data/inputs/{day}"))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    named.sort();
+
+    sets.extend(named.into_iter().map(|input| InputSet {
+        name: file_stem(&input),
+        answers: input.with_extension("answers"),
+        input,
+    }));
+
+    sets
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
@@ -0,0 +1,148 @@
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::{env, fs, process};
+
+use crate::template::Day;
+
+/// Where a solution reads its input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input, `data/inputs/<day>.txt`.
+    Puzzle,
+    /// The example, `data/examples/<day>.txt`.
+    Example,
+    /// An additional example, `data/examples/<day>-<part>.txt`.
+    ExamplePart(u8),
+    /// Any file.
+    File(PathBuf),
+    /// Standard input.
+    Stdin,
+}
+
+impl InputSource {
+    /// Picks the input from the command-line flags `--input <path>`, `--example-part <n>` and
+    /// `--example`, in that order of precedence. A path of `-` stands for standard input.
+    pub fn from_flags(input: Option<String>, example_part: Option<u8>, example: bool) -> Self {
+        match (input, example_part) {
+            (Some(path), _) if path == "-" => Self::Stdin,
+            (Some(path), _) => Self::File(path.into()),
+            (None, Some(part)) => Self::ExamplePart(part),
+            (None, None) if example => Self::Example,
+            (None, None) => Self::Puzzle,
+        }
+    }
+
+    /// Reads the flags from the arguments the current process was started with.
+    pub fn from_env() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let value = |flag: &str| {
+            let i = args.iter().position(|x| x == flag)?;
+            let Some(value) = args.get(i + 1) else {
+                eprintln!("Unexpected command-line input. Missing value for `{flag}`.");
+                process::exit(1);
+            };
+            Some(value.clone())
+        };
+
+        let example_part = value("--example-part").map(|part| {
+            part.parse().unwrap_or_else(|_| {
+                eprintln!("Unexpected command-line input. Format: cargo solve 11 --example-part 2");
+                process::exit(1);
+            })
+        });
+
+        Self::from_flags(
+            value("--input"),
+            example_part,
+            args.iter().any(|x| x == "--example"),
+        )
+    }
+
+    /// The flags that select this input, to pass on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::Example => vec!["--example".into()],
+            Self::ExamplePart(part) => vec!["--example-part".into(), part.to_string()],
+            Self::File(path) => vec!["--input".into(), path.display().to_string()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    pub fn is_puzzle(&self) -> bool {
+        *self == Self::Puzzle
+    }
+
+    pub fn read(&self, day: Day) -> io::Result<String> {
+        match self {
+            Self::Puzzle => fs::read_to_string(format!("data/inputs/{day}.txt")),
+            Self::Example => fs::read_to_string(format!("data/examples/{day}.txt")),
+            Self::ExamplePart(part) => {
+                fs::read_to_string(format!("data/examples/{day}-{part}.txt"))
+            }
+            Self::File(path) => fs::read_to_string(path),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Puzzle => f.write_str("puzzle input"),
+            Self::Example => f.write_str("example"),
+            Self::ExamplePart(part) => write!(f, "example {part}"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Stdin => f.write_str("standard input"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+
+    #[test]
+    fn picks_input_from_flags() {
+        let from = InputSource::from_flags;
+        assert_eq!(from(None, None, false), InputSource::Puzzle);
+        assert_eq!(from(None, None, true), InputSource::Example);
+        assert_eq!(from(None, Some(2), true), InputSource::ExamplePart(2));
+        assert_eq!(from(Some("-".into()), Some(2), true), InputSource::Stdin);
+        assert_eq!(
+            from(Some("in.txt".into()), None, false),
+            InputSource::File("in.txt".into())
+        );
+    }
+
+    #[test]
+    fn round_trips_through_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example,
+            InputSource::ExamplePart(2),
+            InputSource::File("some/input.txt".into()),
+            InputSource::Stdin,
+        ] {
+            let args = source.to_args();
+            let value = |flag: &str| {
+                let i = args.iter().position(|x| x == flag)?;
+                args.get(i + 1).cloned()
+            };
+            let parsed = InputSource::from_flags(
+                value("--input"),
+                value("--example-part").map(|x| x.parse().unwrap()),
+                args.iter().any(|x| x == "--example"),
+            );
+            assert_eq!(parsed, source);
+        }
+    }
+}
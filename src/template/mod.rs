@@ -1,15 +1,20 @@
-use std::{env, fs};
+use std::{env, fs, process};
 
 pub mod aoc_cli;
 pub mod commands;
 pub mod runner;
 
 pub use day::*;
+pub use input::*;
+pub use selection::*;
 
 mod day;
+mod input;
 mod readme_benchmarks;
 mod run_multi;
+mod selection;
 mod timings;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -24,6 +29,17 @@ pub fn read_file(folder: &str, day: Day) -> String {
     f.expect("could not open input file")
 }
 
+/// Reads the input a solution runs against, as selected by the flags the
+/// binary was started with (see [`InputSource::from_env`]). Exits if it can't be read.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let source = InputSource::from_env();
+    source.read(day).unwrap_or_else(|e| {
+        eprintln!("Could not read {source}: {e}");
+        process::exit(1);
+    })
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
@@ -39,6 +55,10 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Parts may return either `Option<T>` or `Result<T, E>` where `E: Display`. If any part returns
+/// an error, the binary exits with a non-zero status after running all parts. Panicking parts and
+/// parts that run longer than `--timeout <seconds>` (60 by default, 0 to disable) count as errors.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -61,8 +81,29 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            // one profiler for the whole process, since a part that timed out
+            // may still be running on its worker thread when the next starts.
+            #[cfg(feature = "dhat-heap")]
+            let profiler = dhat::Profiler::new_heap();
+            // parts run on worker threads that may outlive `main` if they time out.
+            let input: &'static str = $crate::template::read_input(DAY).leak();
+            let mut ok = true;
+            $( ok &= run_part($func, input, DAY, $part); )*
+            // `exit` skips destructors, and the profile is written on drop
+            #[cfg(feature = "dhat-heap")]
+            drop(profiler);
+            if !ok {
+                std::process::exit(1);
+            }
+        }
+
+        /// Feeds mutated examples into every part to check that none of them panic.
+        #[cfg(test)]
+        #[test]
+        fn test_fuzz() {
+            $crate::fuzz::Fuzzer::new(DAY).run(|input| {
+                $( let _ = $func(input); )*
+            });
         }
     };
 }
@@ -1,49 +1,131 @@
-use std::{collections::HashSet, io};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::{fs, io, thread};
 
 use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
 
-use super::{
-    all_days,
-    timings::{Timing, Timings},
-};
-
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
-
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+use super::timings::{Timing, Timings};
+use child_commands::SolutionOutput;
+
+/// Runs the given days and prints their output in order. Prints the total time if `is_timed`
+/// and a summary of the parts that failed, if any. `timeout` is passed on to each solution as
+/// `--timeout`.
+///
+/// With `jobs > 1`, up to `jobs` days run at the same time and each day's output is held back
+/// until the days before it have been printed.
+pub fn run_multi(
+    days_to_run: &BTreeSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    timeout: Option<u64>,
+    jobs: usize,
+) -> Timings {
+    let days: Vec<Day> = days_to_run.iter().copied().collect();
+
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+
+    let mut report = |day: Day, output: SolutionOutput| {
+        if output.lines.is_empty() && output.success {
+            println!("Not solved.");
+        } else {
+            let mut val = child_commands::parse_exec_time(&output.lines, day);
+            if !output.success && val.failed.is_empty() {
+                // the binary died without reporting a failure, e.g. it did not compile or
+                // aborted. Count every part it has that did not get to print a result.
+                let source = fs::read_to_string(get_path_for_bin(day)).unwrap_or_default();
+                let parts = child_commands::solution_parts(&source);
+                val.failed = child_commands::missing_parts(&output.lines, &parts);
             }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
-
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            timings.push(val);
+        }
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
+    if jobs <= 1 {
+        for (i, &day) in days.iter().enumerate() {
+            print_header(day, i > 0);
+            let output =
+                child_commands::run_solution(day, is_timed, is_release, timeout, &[], true)
+                    .unwrap();
+            report(day, output);
+        }
+    } else {
+        let run =
+            |day| child_commands::run_solution(day, is_timed, is_release, timeout, &[], false);
+        run_in_order(&days, jobs, run, |i, output| {
+            let output = output.unwrap();
+            print_header(days[i], i > 0);
+            output.replay();
+            report(days[i], output);
         });
+    }
+
+    let timings = Timings { data: timings };
 
     if is_timed {
-        let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
     }
+
+    if timings.has_failures() {
+        let failures: Vec<String> = timings
+            .failures()
+            .map(|(day, part)| format!("day {day} part {part}"))
+            .collect();
+        println!(
+            "\n{ANSI_BOLD}Failed ({}):{ANSI_RESET} {}",
+            failures.len(),
+            failures.join(", ")
+        );
+    }
+
+    timings
+}
+
+fn print_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Calls `run` for every day on up to `jobs` threads, and `report` with each
+/// result in the original order of `days` as soon as all earlier ones are done.
+fn run_in_order<T: Send>(
+    days: &[Day],
+    jobs: usize,
+    run: impl Fn(Day) -> T + Sync,
+    mut report: impl FnMut(usize, T),
+) {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let (tx, next, run) = (tx.clone(), &next, &run);
+            scope.spawn(move || {
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    if tx.send((day, run(day))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        let mut done = BTreeMap::new();
+        let mut printed = 0;
+        for (day, result) in rx {
+            done.insert(day, result);
+            while let Some(result) = days.get(printed).and_then(|day| done.remove(day)) {
+                report(printed, result);
+                printed += 1;
+            }
+        }
+    });
 }
 
 #[allow(dead_code)]
@@ -59,6 +141,31 @@ impl From<std::io::Error> for Error {
     }
 }
 
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::run_in_order;
+    use crate::template::{Day, all_days};
+
+    #[test]
+    fn reports_in_order() {
+        let days: Vec<Day> = all_days().collect();
+        let mut reported = vec![];
+        run_in_order(
+            &days,
+            4,
+            |day| {
+                // later days finish first.
+                thread::sleep(Duration::from_millis(u64::from(13 - day.into_inner()) * 5));
+                day
+            },
+            |i, day| reported.push((i, day)),
+        );
+        assert_eq!(reported, days.into_iter().enumerate().collect::<Vec<_>>());
+    }
+}
+
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
@@ -68,34 +175,88 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use crate::template::{Day, runner::FAILURE_MARKERS};
     use std::{
+        collections::BTreeMap,
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
+        time::{SystemTime, UNIX_EPOCH},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// The stdout of a solution bin and whether it exited successfully.
+    pub struct SolutionOutput {
+        pub lines: Vec<String>,
+        /// Only filled when the output was captured rather than streamed.
+        pub stderr: Vec<String>,
+        pub success: bool,
+    }
+
+    impl SolutionOutput {
+        /// Prints output that was captured rather than streamed.
+        pub fn replay(&self) {
+            self.lines.iter().for_each(|line| println!("{line}"));
+            self.stderr.iter().for_each(|line| eprintln!("{line}"));
+        }
+    }
+
+    /// Run the solution bin for a given day, passing `extra_args` on to it. With `stream`, its
+    /// output is forwarded as it is written; otherwise it is captured for [`SolutionOutput::replay`].
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        timeout: Option<u64>,
+        extra_args: &[&str],
+        stream: bool,
+    ) -> Result<SolutionOutput, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(SolutionOutput {
+                lines: vec![],
+                stderr: vec![],
+                success: true,
+            });
         }
 
         let day_padded = day.to_string();
+        let timeout = timeout.map(|x| x.to_string());
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
         if is_release {
             args.push("--release");
         }
 
+        args.push("--");
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if let Some(timeout) = &timeout {
+            args.push("--timeout");
+            args.push(timeout);
+        }
+
+        args.extend(extra_args);
+
+        if !stream {
+            let output = Command::new("cargo").args(&args).output()?;
+            let lines = |bytes: Vec<u8>| {
+                String::from_utf8_lossy(&bytes)
+                    .lines()
+                    .map(String::from)
+                    .collect()
+            };
+            return Ok(SolutionOutput {
+                lines: lines(output.stdout),
+                stderr: lines(output.stderr),
+                success: output.status.success(),
+            });
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -123,9 +284,71 @@ pub mod child_commands {
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok(SolutionOutput {
+            lines: output,
+            stderr: vec![],
+            success: status.success(),
+        })
+    }
+
+    /// The parts a solution has, going by its `solution!` invocation: both
+    /// unless a single part is given, as in `solution!(12, 1)`.
+    pub fn solution_parts(source: &str) -> Vec<u8> {
+        let args = source
+            .split_once("solution!(")
+            .and_then(|(_, rest)| rest.split_once(')'))
+            .map_or("", |(args, _)| args);
+        match args.split(',').nth(1).map(str::trim) {
+            Some("1") => vec![1],
+            Some("2") => vec![2],
+            _ => vec![1, 2],
+        }
+    }
+
+    /// The given parts that did not print a result line.
+    pub fn missing_parts(output: &[String], parts: &[u8]) -> Vec<u8> {
+        parts
+            .iter()
+            .copied()
+            .filter(|part| {
+                let prefix = format!("Part {part}: ");
+                !output.iter().any(|l| l.starts_with(&prefix))
+            })
+            .collect()
+    }
+
+    /// The answer printed for each part, without formatting and timings.
+    pub fn parse_answers(output: &[String]) -> BTreeMap<u8, String> {
+        output
+            .iter()
+            .filter_map(|line| {
+                // intermediate results are overwritten with `\r`, the last one is the final result.
+                let line = strip_ansi(line.rsplit('\r').next()?);
+                let (part, answer) = line.strip_prefix("Part ")?.split_once(": ")?;
+                let answer = answer.trim_end();
+                let answer = match answer.rfind(" (") {
+                    Some(i) if answer.ends_with(')') => &answer[..i],
+                    _ => answer,
+                };
+                Some((part.parse().ok()?, answer.trim().to_string()))
+            })
+            .collect()
+    }
+
+    /// Removes terminal escape sequences like `\x1b[1m`.
+    fn strip_ansi(s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            } else {
+                out.push(c);
+            }
+        }
+        out
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -134,8 +357,27 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            failed: vec![],
+            timed_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|x| x.as_secs()),
         };
 
+        for line in output
+            .iter()
+            .filter(|l| FAILURE_MARKERS.iter().any(|m| l.contains(m)))
+        {
+            if let Some(part) = line
+                .split(':')
+                .next()
+                .and_then(|p| p.trim().strip_prefix("Part "))
+                .and_then(|p| p.parse().ok())
+            {
+                timings.failed.push(part);
+            }
+        }
+
         output
             .iter()
             .filter_map(|l| {
@@ -205,7 +447,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{missing_parts, parse_answers, parse_exec_time, solution_parts};
 
         use crate::day;
 
@@ -253,5 +495,60 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_failed_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 7 (1ms @ 10 samples)".into(),
+                    "Part 2: ✖ failed\rPart 2: ✖ failed      ".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), "1ms");
+            assert_eq!(res.part_2, None);
+            assert_eq!(res.failed, vec![2]);
+        }
+
+        #[test]
+        fn parses_panicked_and_timed_out_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: ✖ panicked\rPart 1: ✖ panicked      ".into(),
+                    "Part 2: ✖ timed out after 60s\rPart 2: ✖ timed out after 60s".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.failed, vec![1, 2]);
+        }
+
+        #[test]
+        fn parses_answers() {
+            let answers = parse_answers(&[
+                "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)".into(),
+                "Part 2: ✖ failed\rPart 2: ✖ failed      ".into(),
+                "some debug output".into(),
+            ]);
+            assert_eq!(answers.get(&1).unwrap(), "42");
+            assert_eq!(answers.get(&2).unwrap(), "✖ failed");
+            assert_eq!(answers.len(), 2);
+        }
+
+        #[test]
+        fn finds_missing_parts() {
+            assert_eq!(missing_parts(&[], &[1, 2]), vec![1, 2]);
+            assert_eq!(missing_parts(&["Part 1: 7 (1ms)".into()], &[1, 2]), vec![2]);
+            assert_eq!(missing_parts(&[], &[1]), vec![1]);
+            assert_eq!(missing_parts(&["Part 1: 7 (1ms)".into()], &[1]), vec![]);
+        }
+
+        #[test]
+        fn finds_solution_parts() {
+            assert_eq!(solution_parts("advent_of_code::solution!(3);"), vec![1, 2]);
+            assert_eq!(solution_parts("advent_of_code::solution!(12, 1);"), vec![1]);
+            assert_eq!(solution_parts("advent_of_code::solution!(7, 2);"), vec![2]);
+            assert_eq!(solution_parts(""), vec![1, 2]);
+        }
     }
 }
@@ -2,47 +2,128 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{Write, stdout};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
+use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, InputSource, aoc_cli};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Printed in place of the answer when a part returns an error.
+pub const FAILED: &str = "✖ failed";
+/// Printed in place of the answer when a part panics.
+pub const PANICKED: &str = "✖ panicked";
+/// Printed in place of the answer when a part runs longer than the timeout.
+pub const TIMED_OUT: &str = "✖ timed out";
+
+/// Every marker that `run_multi` counts as a failed part.
+pub const FAILURE_MARKERS: [&str; 3] = [FAILED, PANICKED, TIMED_OUT];
+
+/// Seconds a part may run before it is given up on, unless overridden with `--timeout`.
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// Parts run on a worker thread, which gets as much stack as the main thread usually has.
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// The outcome of running a solution part.
+pub enum Outcome<T> {
+    Solved(T),
+    /// The part returned `None`, usually because it has not been solved yet.
+    Unsolved,
+    /// The part returned an error, rendered with the alternate flag (`{:#}`)
+    /// so that error types which support it include their causes.
+    Failed(String),
+    /// The part panicked. The panic hook has already printed the message.
+    Panicked,
+    /// The part was still running when the timeout ran out.
+    TimedOut(Duration),
+}
+
+/// Types that solution parts may return: `Option<T>` for parts that may not
+/// have an answer yet and `Result<T, E>` for parts that can fail.
+pub trait Answer {
+    type Value: Display;
+
+    fn into_outcome(self) -> Outcome<Self::Value>;
+}
+
+impl<T: Display> Answer for Option<T> {
+    type Value = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        match self {
+            Some(value) => Outcome::Solved(value),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Display> Answer for Result<T, E> {
+    type Value = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        match self {
+            Ok(value) => Outcome::Solved(value),
+            Err(e) => Outcome::Failed(format!("{e:#}")),
+        }
+    }
+}
+
+/// Runs and prints a single part. Returns `false` if the part returned an
+/// error, panicked or timed out.
+pub fn run_part<I, R, F>(func: F, input: I, day: Day, part: u8) -> bool
+where
+    I: Copy + Send + 'static,
+    R: Answer,
+    R::Value: Send + 'static,
+    F: Fn(I) -> R + Send + Sync + 'static,
+{
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples) = run_timed(
+        Arc::new(move |input| func(input).into_outcome()),
+        input,
+        &part_str,
+        |result| print_result(result, &part_str, ""),
+    );
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    match result {
+        Outcome::Solved(result) => {
+            submit_result(result, day, part);
+            true
+        }
+        Outcome::Unsolved => true,
+        Outcome::Failed(_) | Outcome::Panicked | Outcome::TimedOut(_) => false,
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
+///
+/// Only solved parts are benched.
+fn run_timed<I, T, F>(
+    func: Arc<F>,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
-
-        func(input)
-    };
-    let base_time = timer.elapsed();
+    name: &str,
+    hook: impl Fn(&Outcome<T>),
+) -> (Outcome<T>, Duration, u128)
+where
+    I: Copy + Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> Outcome<T> + Send + Sync + 'static,
+{
+    let (result, base_time) = run_isolated(Arc::clone(&func), input, name, timeout());
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let run = if matches!(result, Outcome::Solved(_)) && std::env::args().any(|x| x == "--time") {
+        bench(|input| func(input), input, &base_time)
     } else {
         (base_time, 1)
     };
@@ -50,6 +131,63 @@ fn run_timed<I: Copy, T>(
     (result, run.0, run.1)
 }
 
+/// Runs `func` once on a worker thread named `name`, so that a panic or a
+/// part that never finishes does not take the rest of the run with it. A part
+/// that times out is left running in the background until the process exits.
+fn run_isolated<I, T, F>(
+    func: Arc<F>,
+    input: I,
+    name: &str,
+    timeout: Option<Duration>,
+) -> (Outcome<T>, Duration)
+where
+    I: Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> Outcome<T> + Send + Sync + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let timer = Instant::now();
+
+    thread::Builder::new()
+        .name(name.to_string())
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || {
+            let timer = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| func(input)));
+            let _ = tx.send((result.unwrap_or(Outcome::Panicked), timer.elapsed()));
+        })
+        .expect("failed to spawn worker thread");
+
+    let received = match timeout {
+        Some(timeout) => rx.recv_timeout(timeout),
+        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+
+    match received {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => (Outcome::TimedOut(timer.elapsed()), timer.elapsed()),
+        Err(RecvTimeoutError::Disconnected) => (Outcome::Panicked, timer.elapsed()),
+    }
+}
+
+/// The time limit for a single part, from `--timeout <seconds>`. Zero disables it.
+fn timeout() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+
+    let secs = match args.iter().position(|x| x == "--timeout") {
+        None => DEFAULT_TIMEOUT_SECS,
+        Some(i) => {
+            let Some(secs) = args.get(i + 1).and_then(|x| x.parse::<u64>().ok()) else {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --timeout 60");
+                process::exit(1);
+            };
+            secs
+        }
+    };
+
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
 fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
     let mut stdout = stdout();
 
@@ -90,11 +228,11 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Outcome<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Outcome::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -114,7 +252,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -122,6 +260,34 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Failed(error) => {
+            if is_intermediate_result {
+                print!("{part}: {FAILED}");
+            } else {
+                print!("\r");
+                println!("{part}: {FAILED}      ");
+                for line in error.lines() {
+                    eprintln!("  {line}");
+                }
+            }
+        }
+        Outcome::Panicked => {
+            if is_intermediate_result {
+                print!("{part}: {PANICKED}");
+            } else {
+                print!("\r");
+                println!("{part}: {PANICKED}      ");
+            }
+        }
+        Outcome::TimedOut(after) => {
+            let str = format!("{part}: {TIMED_OUT} after {after:.0?}");
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}");
+            }
+        }
     }
 }
 
@@ -139,6 +305,12 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    let source = InputSource::from_env();
+    if !source.is_puzzle() {
+        eprintln!("Refusing to submit an answer computed from the {source}.");
+        process::exit(1);
+    }
+
     if args.len() < 3 {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
         process::exit(1);
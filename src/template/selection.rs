@@ -0,0 +1,216 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::str::FromStr;
+use std::time::UNIX_EPOCH;
+
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::Timings;
+use crate::template::{Day, all_days};
+
+/// Stored timings above this many milliseconds count as `slow` unless a threshold is given.
+const DEFAULT_SLOW_MILLIS: f64 = 100.0;
+
+/// A set of days picked on the command line, written as a comma-separated list of:
+///
+/// - a day (`9`) or an inclusive range of days (`1-5`),
+/// - `all`,
+/// - `unsolved`: days that do not have stored timings for both parts,
+/// - `slow` or `slow:<ms>`: days whose stored timing is above 100ms or the given threshold,
+/// - `failing`: days with a part that failed the last time they were timed,
+/// - `changed`: days whose solution was modified after they were last timed.
+///
+/// A day is selected if any of the terms matches it.
+///
+/// ```
+/// # use advent_of_code::template::Selection;
+/// assert!("1-5,9".parse::<Selection>().is_ok());
+/// assert!("unsolved,slow:250".parse::<Selection>().is_ok());
+/// assert!("13".parse::<Selection>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selection(Vec<Term>);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Days(Day, Day),
+    All,
+    Unsolved,
+    Slow(f64),
+    Failing,
+    Changed,
+}
+
+impl Selection {
+    pub fn all() -> Self {
+        Self(vec![Term::All])
+    }
+
+    pub fn unsolved() -> Self {
+        Self(vec![Term::Unsolved])
+    }
+
+    /// The days that match, in order. `timings` are the stored timings.
+    pub fn resolve(&self, timings: &Timings) -> BTreeSet<Day> {
+        all_days()
+            .filter(|&day| self.0.iter().any(|term| term.matches(day, timings)))
+            .collect()
+    }
+}
+
+impl Term {
+    fn matches(&self, day: Day, timings: &Timings) -> bool {
+        match *self {
+            Term::Days(first, last) => first <= day && day <= last,
+            Term::All => true,
+            Term::Unsolved => !timings.is_day_complete(day),
+            Term::Slow(millis) => timings
+                .get(day)
+                .is_some_and(|t| t.total_nanos / 1_000_000_f64 > millis),
+            Term::Failing => timings.get(day).is_some_and(|t| !t.failed.is_empty()),
+            Term::Changed => is_changed(day, timings),
+        }
+    }
+}
+
+/// Whether the solution for `day` exists and was modified after it was last timed.
+fn is_changed(day: Day, timings: &Timings) -> bool {
+    let Ok(modified) = fs::metadata(get_path_for_bin(day)).and_then(|m| m.modified()) else {
+        return false;
+    };
+
+    let Some(timed_at) = timings.get(day).and_then(|t| t.timed_at) else {
+        return true;
+    };
+
+    modified
+        .duration_since(UNIX_EPOCH)
+        .is_ok_and(|modified| modified.as_secs() > timed_at)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Selection {
+    type Err = SelectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|term| parse_term(term.trim()))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+fn parse_term(term: &str) -> Result<Term, SelectionFromStrError> {
+    let err = || SelectionFromStrError(term.to_string());
+    let day = |s: &str| s.parse::<Day>().map_err(|_| err());
+
+    Ok(match term {
+        "all" => Term::All,
+        "unsolved" => Term::Unsolved,
+        "slow" => Term::Slow(DEFAULT_SLOW_MILLIS),
+        "failing" => Term::Failing,
+        "changed" => Term::Changed,
+        _ => {
+            if let Some(millis) = term.strip_prefix("slow:") {
+                Term::Slow(millis.parse().map_err(|_| err())?)
+            } else if let Some((first, last)) = term.split_once('-') {
+                let (first, last) = (day(first)?, day(last)?);
+                if first > last {
+                    return Err(err());
+                }
+                Term::Days(first, last)
+            } else {
+                let day = day(term)?;
+                Term::Days(day, day)
+            }
+        }
+    })
+}
+
+/// An error which can be returned when parsing a [`Selection`].
+#[derive(Debug)]
+pub struct SelectionFromStrError(String);
+
+impl Error for SelectionFromStrError {}
+
+impl Display for SelectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection `{}`, expecting days like `1-5,9` or one of \
+            `all`, `unsolved`, `slow`, `slow:<ms>`, `failing` and `changed`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Selection;
+    use crate::day;
+    use crate::template::timings::{Timing, Timings};
+
+    fn timing(day: u8, part_2: Option<&str>, total_nanos: f64, failed: Vec<u8>) -> Timing {
+        Timing {
+            day: crate::template::Day::new(day).unwrap(),
+            part_1: Some("1ms".into()),
+            part_2: part_2.map(String::from),
+            total_nanos,
+            failed,
+            timed_at: None,
+        }
+    }
+
+    fn select(selection: &str, timings: &Timings) -> Vec<u8> {
+        selection
+            .parse::<Selection>()
+            .unwrap()
+            .resolve(timings)
+            .into_iter()
+            .map(|day| day.into_inner())
+            .collect()
+    }
+
+    #[test]
+    fn selects_days_and_ranges() {
+        let timings = Timings::default();
+        assert_eq!(select("1-3,9", &timings), vec![1, 2, 3, 9]);
+        assert_eq!(select(" 5 , 4-5 ", &timings), vec![4, 5]);
+        assert_eq!(select("all", &timings).len(), 12);
+        assert_eq!(Selection::all().resolve(&timings).len(), 12);
+    }
+
+    #[test]
+    fn selects_from_timings() {
+        let timings = Timings {
+            data: vec![
+                timing(1, Some("1ms"), 2e6, vec![]),
+                timing(2, None, 5e8, vec![2]),
+                timing(3, Some("1s"), 1.5e9, vec![]),
+            ],
+        };
+        assert_eq!(
+            select("unsolved", &timings),
+            vec![2, 4, 5, 6, 7, 8, 9, 10, 11, 12]
+        );
+        assert_eq!(select("slow", &timings), vec![2, 3]);
+        assert_eq!(select("slow:1000", &timings), vec![3]);
+        assert_eq!(select("failing", &timings), vec![2]);
+        assert_eq!(select("failing,1", &timings), vec![1, 2]);
+        assert_eq!(
+            Selection::unsolved().resolve(&timings).first(),
+            Some(&day!(2))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_selections() {
+        for s in ["", "0", "13", "5-3", "1-", "slow:", "slow:fast", "solved"] {
+            assert!(s.parse::<Selection>().is_err(), "{s:?}");
+        }
+    }
+}
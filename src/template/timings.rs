@@ -12,6 +12,10 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Parts that returned an error instead of an answer.
+    pub failed: Vec<u8>,
+    /// When the day was timed, in seconds since the Unix epoch.
+    pub timed_at: Option<u64>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -65,6 +69,21 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Every `(day, part)` that returned an error, in order.
+    pub fn failures(&self) -> impl Iterator<Item = (Day, u8)> + '_ {
+        self.data
+            .iter()
+            .flat_map(|t| t.failed.iter().map(move |&part| (t.day, part)))
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.failures().next().is_some()
+    }
+
+    pub fn get(&self, day: Day) -> Option<&Timing> {
+        self.data.iter().find(|t| t.day == day)
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -133,6 +152,21 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "failed".into(),
+            JsonValue::Array(
+                value
+                    .failed
+                    .iter()
+                    .map(|&part| JsonValue::Number(part.into()))
+                    .collect(),
+            ),
+        );
+
+        if let Some(timed_at) = value.timed_at {
+            map.insert("timed_at".into(), JsonValue::Number(timed_at as f64));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +200,37 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // older timing files do not record failures.
+        let failed = match json.get("failed") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .and_then(|parts| {
+                    parts
+                        .iter()
+                        .map(|part| part.get::<f64>().map(|&x| x as u8))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or("Expected timing.failed to be an array of numbers.")?,
+        };
+
+        let timed_at = match json.get("timed_at") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<f64>()
+                    .map(|&x| x as u64)
+                    .ok_or("Expected timing.timed_at to be a number.")?,
+            ),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            failed,
+            timed_at,
         })
     }
 }
@@ -191,18 +251,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    failed: vec![],
+                    timed_at: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    failed: vec![],
+                    timed_at: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    failed: vec![],
+                    timed_at: None,
                 },
             ],
         }
@@ -223,6 +289,14 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_failed_parts() {
+            let json = r#"{ "data": [{ "day": "03", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "failed": [2] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].failed, vec![2]);
+            assert_eq!(timings.failures().collect::<Vec<_>>(), vec![(day!(3), 2)]);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -282,10 +356,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    failed: vec![],
+                    timed_at: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -296,10 +372,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    failed: vec![],
+                    timed_at: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,10 +388,12 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    failed: vec![],
+                    timed_at: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -334,6 +414,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    failed: vec![],
+                    timed_at: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +436,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    failed: vec![],
+                    timed_at: None,
                 }],
             };
             let merged = timings.merge(&other);
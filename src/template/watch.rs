@@ -0,0 +1,121 @@
+/// Re-runs a day whenever its solution, example or input changes.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::run_multi::{child_commands, get_path_for_bin};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, InputSource};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The answers of a run, keyed by input (`Example` or `Input`) and part.
+type Answers = BTreeMap<(&'static str, u8), String>;
+
+/// Runs `day` against its example and then `input` every time one of the
+/// files changes, and prints which answers changed since the previous run.
+/// Runs until interrupted.
+pub fn watch(day: Day, is_release: bool, timeout: Option<u64>, input: &InputSource) -> ! {
+    let input_file = match input {
+        InputSource::ExamplePart(part) => Some(format!("./data/examples/{day}-{part}.txt").into()),
+        InputSource::File(path) => Some(path.clone()),
+        InputSource::Stdin | InputSource::Example => None,
+        InputSource::Puzzle => Some(format!("./data/inputs/{day}.txt").into()),
+    };
+    let files: Vec<PathBuf> = [
+        Some(get_path_for_bin(day).into()),
+        Some(format!("./data/examples/{day}.txt").into()),
+        input_file,
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let mut last_modified = None;
+    let mut previous = Answers::new();
+
+    loop {
+        let modified: Vec<Option<SystemTime>> = files
+            .iter()
+            .map(|file| fs::metadata(file).and_then(|m| m.modified()).ok())
+            .collect();
+
+        if last_modified.as_ref() != Some(&modified) {
+            last_modified = Some(modified);
+
+            // clear the screen so that only the latest run is visible.
+            print!("\x1b[2J\x1b[H");
+            println!(
+                "{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}(watching for changes, press Ctrl-C to stop){ANSI_RESET}"
+            );
+
+            if let Some(answers) = run_once(day, is_release, timeout, input) {
+                print_changes(&previous, &answers);
+                previous = answers;
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Runs the example and then `input`. Returns `None` if the solution did not build.
+fn run_once(
+    day: Day,
+    is_release: bool,
+    timeout: Option<u64>,
+    input: &InputSource,
+) -> Option<Answers> {
+    let mut answers = Answers::new();
+
+    let mut runs = vec![("Example", InputSource::Example)];
+    if *input != InputSource::Example {
+        runs.push(("Input", input.clone()));
+    }
+
+    for (label, source) in runs {
+        println!("\n{ANSI_BOLD}{label}{ANSI_RESET} {ANSI_ITALIC}({source}){ANSI_RESET}");
+
+        let args = source.to_args();
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let output =
+            child_commands::run_solution(day, false, is_release, timeout, &args, true).ok()?;
+
+        if output.lines.is_empty() && !output.success {
+            return None;
+        }
+
+        answers.extend(
+            child_commands::parse_answers(&output.lines)
+                .into_iter()
+                .map(|(part, answer)| ((label, part), answer)),
+        );
+    }
+
+    Some(answers)
+}
+
+fn print_changes(previous: &Answers, answers: &Answers) {
+    if previous.is_empty() {
+        return;
+    }
+
+    let changes: Vec<String> = answers
+        .iter()
+        .filter_map(|(key @ (label, part), answer)| {
+            let old = previous.get(key).map_or("-", String::as_str);
+            (old != answer)
+                .then(|| format!("{label} part {part}: {old} → {ANSI_BOLD}{answer}{ANSI_RESET}"))
+        })
+        .collect();
+
+    println!();
+    if changes.is_empty() {
+        println!("{ANSI_ITALIC}Answers unchanged since the last run.{ANSI_RESET}");
+    } else {
+        println!("{ANSI_BOLD}Changed since the last run:{ANSI_RESET}");
+        changes.iter().for_each(|change| println!("  {change}"));
+    }
+}